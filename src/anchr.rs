@@ -3,28 +3,56 @@ use clap::*;
 
 mod cmd;
 
-fn main() -> std::io::Result<()> {
-    let app = App::new("anchr")
+// All subcommands, also used by `anchr man`
+fn subcommands<'a, 'b>() -> Vec<App<'a, 'b>> {
+    vec![
+        cmd::anchors::make_subcommand(),
+        cmd::batch::make_subcommand(),
+        cmd::compare::make_subcommand(),
+        cmd::completions::make_subcommand(),
+        cmd::dep::make_subcommand(),
+        cmd::ena::make_subcommand(),
+        cmd::kmer_profile::make_subcommand(),
+        cmd::man::make_subcommand(),
+        cmd::merge::make_subcommand(),
+        cmd::qtrim::make_subcommand(),
+        cmd::quorum::make_subcommand(),
+        cmd::reads::make_subcommand(),
+        cmd::report::make_subcommand(),
+        cmd::select::make_subcommand(),
+        cmd::template::make_subcommand(),
+        cmd::trim::make_subcommand(),
+        cmd::unitigs::make_subcommand(),
+    ]
+}
+
+fn build_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("anchr")
         .version(crate_version!())
         .author(crate_authors!())
         .about("Anchr - Assembler of N-free CHRomosomes")
         .setting(AppSettings::ArgRequiredElseHelp)
-        .subcommand(cmd::anchors::make_subcommand())
-        .subcommand(cmd::dep::make_subcommand())
-        .subcommand(cmd::ena::make_subcommand())
-        .subcommand(cmd::merge::make_subcommand())
-        .subcommand(cmd::quorum::make_subcommand())
-        .subcommand(cmd::template::make_subcommand())
-        .subcommand(cmd::trim::make_subcommand())
-        .subcommand(cmd::unitigs::make_subcommand());
+        .subcommands(subcommands())
+}
 
+fn main() -> std::io::Result<()> {
+    let app = build_app();
     // Check which subcomamnd the user ran...
     match app.get_matches().subcommand() {
         ("anchors", Some(sub_matches)) => cmd::anchors::execute(sub_matches),
+        ("batch", Some(sub_matches)) => cmd::batch::execute(sub_matches),
+        ("compare", Some(sub_matches)) => cmd::compare::execute(sub_matches),
+        ("completions", Some(sub_matches)) => cmd::completions::execute(sub_matches, build_app()),
         ("dep", Some(sub_matches)) => cmd::dep::execute(sub_matches),
         ("ena", Some(sub_matches)) => cmd::ena::execute(sub_matches),
+        ("kmer-profile", Some(sub_matches)) => cmd::kmer_profile::execute(sub_matches),
+        ("man", Some(sub_matches)) => cmd::man::execute(sub_matches, build_app(), subcommands()),
         ("merge", Some(sub_matches)) => cmd::merge::execute(sub_matches),
+        ("qtrim", Some(sub_matches)) => cmd::qtrim::execute(sub_matches),
         ("quorum", Some(sub_matches)) => cmd::quorum::execute(sub_matches),
+        ("reads", Some(sub_matches)) => cmd::reads::execute(sub_matches),
+        ("report", Some(sub_matches)) => cmd::report::execute(sub_matches),
+        ("select", Some(sub_matches)) => cmd::select::execute(sub_matches),
         ("template", Some(sub_matches)) => cmd::template::execute(sub_matches),
         ("trim", Some(sub_matches)) => cmd::trim::execute(sub_matches),
         ("unitigs", Some(sub_matches)) => cmd::unitigs::execute(sub_matches),
@@ -1,7 +1,6 @@
+use anchr::AnchorsOptions;
 use clap::*;
 use itertools::Itertools;
-use std::collections::HashMap;
-use tera::{Context, Tera};
 
 // Create clap subcommand arguments
 pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
@@ -15,6 +14,13 @@ Fasta files can‘t be gzipped
 
 To get single-copy regions, set --uscale to 1.5
 
+With --organelle, contigs of the others set with a mean coverage of at least
+--orgratio times the median are written to organelle.fasta as candidates of
+mitochondria, chloroplasts or plasmids, along with organelle.tsv
+    * ratio, mean coverage of the contig / median of all contigs
+    * overlap, length of the overlapping ends, > 0 means circular
+    * --gc "0.2-0.45" restricts candidates to this range of GC content
+
 "#,
         )
         .arg(
@@ -98,6 +104,26 @@ To get single-copy regions, set --uscale to 1.5
                 .long("keepedge")
                 .help("Keep edges of anchors"),
         )
+        .arg(
+            Arg::with_name("organelle")
+                .long("organelle")
+                .help("Write high-copy contigs to organelle.fasta"),
+        )
+        .arg(
+            Arg::with_name("orgratio")
+                .long("orgratio")
+                .help("Minimal coverage multiple of organelles to the median")
+                .takes_value(true)
+                .default_value("5")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("gc")
+                .long("gc")
+                .help("Range of GC content of organelles, e.g. 0.2-0.45")
+                .takes_value(true)
+                .empty_values(false),
+        )
         .arg(
             Arg::with_name("parallel")
                 .long("parallel")
@@ -122,53 +148,37 @@ To get single-copy regions, set --uscale to 1.5
 pub fn execute(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
     let mut writer = intspan::writer(args.value_of("outfile").unwrap());
 
-    // context from args
-    let mut opt = HashMap::new();
-    opt.insert("min", args.value_of("min").unwrap());
-    opt.insert("mincov", args.value_of("mincov").unwrap());
-    opt.insert("readl", args.value_of("readl").unwrap());
-    opt.insert("mscale", args.value_of("mscale").unwrap());
-    opt.insert("lscale", args.value_of("lscale").unwrap());
-    opt.insert("uscale", args.value_of("uscale").unwrap());
-    opt.insert("ratio", args.value_of("ratio").unwrap());
-    opt.insert("fill", args.value_of("fill").unwrap());
-
-    opt.insert(
-        "longest",
-        if args.is_present("longest") { "1" } else { "0" },
-    );
-    opt.insert(
-        "keepedge",
-        if args.is_present("keepedge") {
-            "1"
-        } else {
-            "0"
-        },
-    );
-
-    opt.insert("parallel", args.value_of("parallel").unwrap());
-
-    let infiles = args.values_of("infiles").unwrap().collect_vec();
-
-    let mut context = Context::new();
-    context.insert("opt", &opt);
-    context.insert("args", &infiles);
-
-    // eprintln!("{:#?}", context);
-
-    // many templates
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/anchors.tera.sh")),
-    ])
-    .unwrap();
-
-    // eprintln!("{:#?}", tera);
-
-    let rendered = tera.render("t", &context).unwrap();
-
-    writer.write_all(rendered.as_ref())?;
+    // options from args
+    let mut opt = AnchorsOptions::new(&args.values_of("infiles").unwrap().collect_vec());
+    opt.min = value_t!(args, "min", usize).unwrap_or_else(|e| e.exit());
+    opt.mincov = value_t!(args, "mincov", usize).unwrap_or_else(|e| e.exit());
+    opt.readl = value_t!(args, "readl", usize).unwrap_or_else(|e| e.exit());
+    opt.mscale = value_t!(args, "mscale", f64).unwrap_or_else(|e| e.exit());
+    opt.lscale = value_t!(args, "lscale", f64).unwrap_or_else(|e| e.exit());
+    opt.uscale = value_t!(args, "uscale", f64).unwrap_or_else(|e| e.exit());
+    opt.fill = value_t!(args, "fill", usize).unwrap_or_else(|e| e.exit());
+    opt.ratio = value_t!(args, "ratio", f64).unwrap_or_else(|e| e.exit());
+    opt.longest = args.is_present("longest");
+    opt.keepedge = args.is_present("keepedge");
+    opt.organelle = args.is_present("organelle");
+    opt.orgratio = value_t!(args, "orgratio", f64).unwrap_or_else(|e| e.exit());
+
+    if let Some(gc) = args.value_of("gc") {
+        let range = gc
+            .split_once('-')
+            .and_then(|(min, max)| Some((min.parse::<f64>().ok()?, max.parse::<f64>().ok()?)));
+        match range {
+            Some(r) => opt.gc = Some(r),
+            None => {
+                eprintln!("Need a range like 0.2-0.45 for --gc");
+                std::process::exit(1)
+            }
+        }
+    }
+
+    opt.parallel = value_t!(args, "parallel", usize).unwrap_or_else(|e| e.exit());
+
+    writer.write_all(opt.render()?.as_ref())?;
 
     Ok(())
 }
@@ -0,0 +1,354 @@
+use anchr::libs::template;
+use clap::*;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+// Create clap subcommand arguments
+pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("batch")
+        .about("Creates projects of multiple samples")
+        .after_help(
+            r#"
+<samplesheet> can be a TSV, a CSV or the YAML from `anchr ena info`
+
+* TSV/CSV, the first line is a header
+
+    * name   - name of the sample and the project directory
+    * R1, R2 - paired reads. S1, S2, T1 and T2 for extra libraries
+               With R1 only, `--se` is added
+    * genome - the haploid genome size. Defaults to --genome of template
+    * Other columns are per-sample options of `anchr template`
+        * empty or `false` - omitted
+        * `true`           - `--column`, a flag, e.g. `merge`
+        * others           - `--column value`, e.g. `cov` with `40 80`
+
+* YAML from `anchr ena info`
+
+    * Runs of each sample are linked as R, S and T libraries, at most 3 runs
+    * Reads are `<SRR>_1.fastq.gz` and `<SRR>_2.fastq.gz` in --ena
+    * Runs of a sample should be all SINGLE or all PAIRED
+
+Options after `--` are passed to `anchr template` of all samples.
+Per-sample values, including `genome`, replace them
+
+    anchr batch samples.tsv -- --parallel 24 --merge --extend
+
+"#,
+        )
+        .arg(
+            Arg::with_name("infile")
+                .help("Sets the sample sheet to use")
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("dir")
+                .long("dir")
+                .short("d")
+                .help("Where to create project directories")
+                .takes_value(true)
+                .default_value(".")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("ena")
+                .long("ena")
+                .help("Directory of downloaded ENA reads. Defaults to the one of the YAML")
+                .takes_value(true)
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("template")
+                .help("Options of `anchr template`")
+                .multiple(true)
+                .last(true),
+        )
+}
+
+#[derive(Default, Debug)]
+struct Sample {
+    name: String,
+    reads: Vec<(String, PathBuf)>,
+    genome: Option<String>,
+    opts: Vec<(String, String)>,
+}
+
+// command implementation
+pub fn execute(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
+    let infile = args.value_of("infile").unwrap();
+    let sheet_dir = Path::new(infile)
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_default();
+
+    let samples = if infile.ends_with(".yml") || infile.ends_with(".yaml") {
+        let ena_dir = match args.value_of("ena") {
+            Some(d) => PathBuf::from(d),
+            None => sheet_dir,
+        };
+        read_ena_yaml(infile, &ena_dir)?
+    } else {
+        let delimiter = if infile.ends_with(".csv") {
+            b','
+        } else {
+            b'\t'
+        };
+        read_sheet(infile, &sheet_dir, delimiter)?
+    };
+
+    // Options after `--`, checked once for all samples
+    let common: Vec<String> = match args.values_of("template") {
+        Some(values) => values.map(|v| v.to_string()).collect(),
+        None => vec![],
+    };
+    template_matches(&common).map_err(|e| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Options of `anchr template`: {}", e),
+        )
+    })?;
+
+    //----------------------------
+    // projects
+    //----------------------------
+    // A failed sample is reported and skipped, others are still created
+    let dir = PathBuf::from(args.value_of("dir").unwrap());
+    fs::create_dir_all(&dir)?;
+
+    let mut created = vec![];
+    let mut failed = vec![];
+    for sample in &samples {
+        let project = dir.join(&sample.name);
+        eprintln!("==> {}", project.display());
+
+        match create_project(sample, &common, &project) {
+            Ok(names) => {
+                for name in names {
+                    eprintln!("Create {}", name);
+                }
+                created.push(sample.name.as_str());
+            }
+            Err(e) => {
+                eprintln!("{}: {}", sample.name, e);
+                failed.push(sample.name.as_str());
+            }
+        }
+    }
+
+    //----------------------------
+    // driver and report
+    //----------------------------
+    for (outname, content) in template::render_batch(&created)? {
+        eprintln!("Create {}", outname);
+        fs::write(dir.join(&outname), content)?;
+    }
+
+    if !failed.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "{} of {} samples failed: {}",
+                failed.len(),
+                samples.len(),
+                failed.join(" ")
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+// Links reads and writes scripts of `anchr template` into the project
+fn create_project(
+    sample: &Sample,
+    common: &[String],
+    project: &Path,
+) -> std::result::Result<Vec<String>, std::io::Error> {
+    fs::create_dir_all(project.join("2_illumina"))?;
+    for (link, target) in &sample.reads {
+        let link = project.join("2_illumina").join(format!("{}.fq.gz", link));
+        if fs::symlink_metadata(&link).is_ok() {
+            fs::remove_file(&link)?;
+        }
+        symlink(target, &link)?;
+    }
+
+    // Values of the sample come later and replace common ones
+    // `--key=value`, as values like `--dedupe` of `trim` start with dashes
+    let mut argv = common.to_vec();
+    if let Some(genome) = &sample.genome {
+        argv.push(format!("--genome={}", genome));
+    }
+    for (key, value) in &sample.opts {
+        if value == "true" {
+            argv.push(format!("--{}", key));
+        } else {
+            argv.push(format!("--{}={}", key, value));
+        }
+    }
+
+    let matches = template_matches(&argv)?;
+    let opt = super::template::options(&matches)?;
+
+    // The same as running `anchr template` in the project
+    let command = format!("anchr template {}", argv.join(" "));
+    opt.write_project(project, Some(command.trim_end()))
+}
+
+// Later values of an option replace earlier ones. Errors are the first line without usages
+fn template_matches(argv: &[String]) -> std::result::Result<ArgMatches<'static>, std::io::Error> {
+    super::template::make_subcommand()
+        .setting(AppSettings::AllArgsOverrideSelf)
+        .get_matches_from_safe(std::iter::once("template").chain(argv.iter().map(|a| a.as_str())))
+        .map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                e.message.lines().next().unwrap_or_default().to_string(),
+            )
+        })
+}
+
+fn read_sheet(
+    infile: &str,
+    sheet_dir: &Path,
+    delimiter: u8,
+) -> std::result::Result<Vec<Sample>, std::io::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .comment(Some(b'#'))
+        .flexible(true)
+        .from_reader(intspan::reader(infile));
+    let headers: Vec<String> = reader
+        .headers()?
+        .iter()
+        .map(|s| s.trim().to_string())
+        .collect();
+
+    let mut samples = vec![];
+    for record in reader.records() {
+        let record = record?;
+        let mut sample = Sample::default();
+
+        for (i, value) in record.iter().enumerate() {
+            let value = value.trim();
+            if value.is_empty() || value == "false" || i >= headers.len() {
+                continue;
+            }
+            match headers[i].as_str() {
+                "name" => sample.name = value.to_string(),
+                "genome" => sample.genome = Some(value.to_string()),
+                "R1" | "R2" | "S1" | "S2" | "T1" | "T2" => sample
+                    .reads
+                    .push((headers[i].to_string(), resolve(sheet_dir, value))),
+                key => sample.opts.push((key.to_string(), value.to_string())),
+            }
+        }
+
+        if sample.name.is_empty() {
+            continue;
+        }
+        // R1 only
+        let paired = sample.reads.iter().any(|(r, _)| r == "R2");
+        if !paired && !sample.opts.iter().any(|(k, _)| k == "se") {
+            sample.opts.push(("se".to_string(), "true".to_string()));
+        }
+        samples.push(sample);
+    }
+
+    Ok(samples)
+}
+
+// name => SRX => { library_layout, srr, ... }
+// All runs of a sample should have the same layout, as `--se` is for the whole project
+fn read_ena_yaml(infile: &str, ena_dir: &Path) -> std::result::Result<Vec<Sample>, std::io::Error> {
+    let yaml = intspan::read_yaml(infile);
+
+    let mut samples = vec![];
+    for (name, experiments) in &yaml {
+        let mut sample = Sample {
+            name: name.to_string(),
+            ..Default::default()
+        };
+
+        let mut prefixes = vec!["R", "S", "T"].into_iter();
+        let mut layout: Option<bool> = None;
+        let experiments = match experiments.as_mapping() {
+            Some(m) => m,
+            None => continue,
+        };
+        let n_runs: usize = experiments
+            .iter()
+            .filter_map(|(_, info)| info["srr"].as_sequence())
+            .map(|s| s.iter().filter(|srr| srr.as_str().is_some()).count())
+            .sum();
+        if n_runs > prefixes.len() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "{}: {} runs, but only R, S and T libraries are supported",
+                    name, n_runs
+                ),
+            ));
+        }
+        for (_, info) in experiments {
+            let srrs = match info["srr"].as_sequence() {
+                Some(s) => s,
+                None => continue,
+            };
+            let single = info["library_layout"].as_str() == Some("SINGLE");
+            if layout.is_some_and(|l| l != single) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{}: runs of both SINGLE and PAIRED layouts", name),
+                ));
+            }
+            layout = Some(single);
+
+            for srr in srrs.iter().filter_map(|s| s.as_str()) {
+                let prefix = prefixes.next().unwrap();
+                if single {
+                    sample.reads.push((
+                        format!("{}1", prefix),
+                        resolve(ena_dir, &format!("{}.fastq.gz", srr)),
+                    ));
+                } else {
+                    for i in 1..=2 {
+                        sample.reads.push((
+                            format!("{}{}", prefix, i),
+                            resolve(ena_dir, &format!("{}_{}.fastq.gz", srr, i)),
+                        ));
+                    }
+                }
+            }
+        }
+        if layout == Some(true) {
+            sample.opts.push(("se".to_string(), "true".to_string()));
+        }
+
+        samples.push(sample);
+    }
+
+    Ok(samples)
+}
+
+// Relative paths in the sample sheet are relative to the sheet itself
+fn resolve(base: &Path, file: &str) -> PathBuf {
+    let path = base.join(file);
+    match fs::canonicalize(&path) {
+        Ok(p) => p,
+        Err(_) => match env::current_dir() {
+            Ok(d) => d.join(path),
+            Err(_) => path,
+        },
+    }
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(target, link)
+}
@@ -0,0 +1,403 @@
+use clap::*;
+use std::collections::{BTreeMap, HashSet};
+use std::io::BufRead;
+use std::path::Path;
+
+// Create clap subcommand arguments
+pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("compare")
+        .about("Compares two project directories")
+        .after_help(
+            r#"
+Lines up the same stages of two runs, e.g. with different --qual, --cov or --unitigger
+
+Table: compareStats
+    Numeric columns of stat*.tsv presenting in both directories,
+    rows are matched by names. See `anchr report --table` for the columns
+
+Table: compareRuntime
+    Wall time of each script, recorded in `resources/`
+
+Table: compareAnchors
+    Anchors of one run are compared with k-mers of anchors of the other run
+    * shared, containment >= --shared
+    * unique, containment <= --unique
+    * discordant, all others. They are listed in Table: compareDiscordant
+
+"#,
+        )
+        .arg(
+            Arg::with_name("dir_a")
+                .help("The first project directory")
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("dir_b")
+                .help("The second project directory")
+                .required(true)
+                .index(2),
+        )
+        .arg(
+            Arg::with_name("anchors")
+                .long("anchors")
+                .help("Anchors of each directory")
+                .takes_value(true)
+                .default_value("7_merge_anchors/anchor.merge.fasta")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("kmer")
+                .long("kmer")
+                .short("k")
+                .help("K-mer size, <= 31")
+                .takes_value(true)
+                .default_value("31")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("shared")
+                .long("shared")
+                .help("Minimal containment of shared anchors")
+                .takes_value(true)
+                .default_value("0.95")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("unique")
+                .long("unique")
+                .help("Maximal containment of unique anchors")
+                .takes_value(true)
+                .default_value("0.05")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("outfile")
+                .short("o")
+                .long("outfile")
+                .takes_value(true)
+                .default_value("stdout")
+                .empty_values(false)
+                .help("Output filename. [stdout] for screen"),
+        )
+}
+
+// command implementation
+pub fn execute(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
+    let dir_a = Path::new(args.value_of("dir_a").unwrap());
+    let dir_b = Path::new(args.value_of("dir_b").unwrap());
+    for dir in &[dir_a, dir_b] {
+        if !dir.is_dir() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Can't find {}", dir.display()),
+            ));
+        }
+    }
+
+    let kmer = value_t!(args.value_of("kmer"), usize).unwrap_or_else(|e| e.exit());
+    if kmer == 0 || kmer > 31 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "--kmer should be in 1-31",
+        ));
+    }
+    let shared = value_t!(args.value_of("shared"), f64).unwrap_or_else(|e| e.exit());
+    let unique = value_t!(args.value_of("unique"), f64).unwrap_or_else(|e| e.exit());
+
+    let mut writer = intspan::writer(args.value_of("outfile").unwrap());
+
+    //----------------------------
+    // Stats
+    //----------------------------
+    writer.write_all("Table: compareStats\n\n".as_ref())?;
+    writer.write_all("| Table | Name | Key | A | B | Delta | Delta% |\n".as_ref())?;
+    writer.write_all("|:--|:--|:--|--:|--:|--:|--:|\n".as_ref())?;
+    for line in compare_stats(dir_a, dir_b)? {
+        writer.write_all((line + "\n").as_ref())?;
+    }
+
+    //----------------------------
+    // Runtime
+    //----------------------------
+    let walls_a = super::report::script_walls(dir_a);
+    let walls_b = super::report::script_walls(dir_b);
+    if !walls_a.is_empty() || !walls_b.is_empty() {
+        writer.write_all("\nTable: compareRuntime\n\n".as_ref())?;
+        writer.write_all("| Script | A | B | Delta | Delta% |\n".as_ref())?;
+        writer.write_all("|:--|--:|--:|--:|--:|\n".as_ref())?;
+
+        let mut scripts: Vec<&String> = walls_a.keys().chain(walls_b.keys()).collect();
+        scripts.sort();
+        scripts.dedup();
+        for script in scripts {
+            let a = walls_a.get(script).copied();
+            let b = walls_b.get(script).copied();
+            let duration = |v: Option<f64>| {
+                v.map(super::report::format_duration)
+                    .unwrap_or_else(|| "-".to_string())
+            };
+            let (delta, pct) = match (a, b) {
+                (Some(a), Some(b)) => {
+                    let sign = if b < a { "-" } else { "+" };
+                    (
+                        format!("{}{}", sign, super::report::format_duration((b - a).abs())),
+                        format_pct(a, b),
+                    )
+                }
+                _ => ("-".to_string(), "-".to_string()),
+            };
+            writer.write_all(
+                format!(
+                    "| {} | {} | {} | {} | {} |\n",
+                    script,
+                    duration(a),
+                    duration(b),
+                    delta,
+                    pct
+                )
+                .as_ref(),
+            )?;
+        }
+    }
+
+    //----------------------------
+    // Anchors
+    //----------------------------
+    let anchors = args.value_of("anchors").unwrap();
+    let (file_a, file_b) = (dir_a.join(anchors), dir_b.join(anchors));
+    if file_a.is_file() && file_b.is_file() {
+        let seqs_a = read_fasta(&file_a);
+        let seqs_b = read_fasta(&file_b);
+        let kmers_a = kmer_set(&seqs_a, kmer);
+        let kmers_b = kmer_set(&seqs_b, kmer);
+
+        let classes_a = classify(&seqs_a, &kmers_b, kmer, shared, unique);
+        let classes_b = classify(&seqs_b, &kmers_a, kmer, shared, unique);
+
+        writer.write_all("\nTable: compareAnchors\n\n".as_ref())?;
+        writer.write_all(
+            "| Set | # | Sum | Shared | Sum | Discordant | Sum | Unique | Sum |\n".as_ref(),
+        )?;
+        writer.write_all("|:--|--:|--:|--:|--:|--:|--:|--:|--:|\n".as_ref())?;
+        for (set, classes) in &[("A", &classes_a), ("B", &classes_b)] {
+            let mut counts = [(0, 0); 3];
+            for c in classes.iter() {
+                counts[c.class as usize].0 += 1;
+                counts[c.class as usize].1 += c.length;
+            }
+            writer.write_all(
+                format!(
+                    "| {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+                    set,
+                    classes.len(),
+                    classes.iter().map(|c| c.length).sum::<usize>(),
+                    counts[0].0,
+                    counts[0].1,
+                    counts[1].0,
+                    counts[1].1,
+                    counts[2].0,
+                    counts[2].1,
+                )
+                .as_ref(),
+            )?;
+        }
+
+        writer.write_all("\nTable: compareDiscordant\n\n".as_ref())?;
+        writer.write_all("| Set | Name | Length | Containment |\n".as_ref())?;
+        writer.write_all("|:--|:--|--:|--:|\n".as_ref())?;
+        for (set, classes) in &[("A", &classes_a), ("B", &classes_b)] {
+            for c in classes.iter().filter(|c| c.class == Class::Discordant) {
+                writer.write_all(
+                    format!(
+                        "| {} | {} | {} | {:.4} |\n",
+                        set, c.name, c.length, c.containment
+                    )
+                    .as_ref(),
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Rows of the same table and the same name
+fn compare_stats(dir_a: &Path, dir_b: &Path) -> std::result::Result<Vec<String>, std::io::Error> {
+    let mut tsvs: Vec<String> = std::fs::read_dir(dir_a)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|f| f.starts_with("stat") && f.ends_with(".tsv"))
+        .filter(|f| dir_b.join(f).is_file())
+        .collect();
+    tsvs.sort();
+
+    let mut lines = vec![];
+    for f in tsvs {
+        let (table_a, table_b) = match (
+            super::report::parse_table(&dir_a.join(&f)),
+            super::report::parse_table(&dir_b.join(&f)),
+        ) {
+            (Ok(a), Ok(b)) => (a, b),
+            _ => continue,
+        };
+
+        let rows_b: BTreeMap<String, &Vec<Option<String>>> = table_b
+            .rows
+            .iter()
+            .map(|r| (r[0].clone().unwrap_or_default(), r))
+            .collect();
+
+        for row_a in &table_a.rows {
+            let name = row_a[0].clone().unwrap_or_default();
+            let row_b = match rows_b.get(&name) {
+                Some(r) => r,
+                None => continue,
+            };
+
+            for (i, key) in table_a.keys.iter().enumerate().skip(1) {
+                let j = match table_b.keys.iter().position(|k| k == key) {
+                    Some(j) => j,
+                    None => continue,
+                };
+                let a = row_a.get(i).cloned().flatten();
+                let b = row_b.get(j).cloned().flatten();
+                let (a, b) = match (a, b) {
+                    (Some(a), Some(b)) => (a, b),
+                    _ => continue,
+                };
+                let (va, vb) = match (a.parse::<f64>(), b.parse::<f64>()) {
+                    (Ok(va), Ok(vb)) => (va, vb),
+                    _ => continue,
+                };
+
+                lines.push(format!(
+                    "| {} | {} | {} | {} | {} | {} | {} |",
+                    table_a.name,
+                    name,
+                    key,
+                    a,
+                    b,
+                    format_delta(va, vb),
+                    format_pct(va, vb)
+                ));
+            }
+        }
+    }
+
+    Ok(lines)
+}
+
+fn format_delta(a: f64, b: f64) -> String {
+    let delta = b - a;
+    if a.fract() == 0.0 && b.fract() == 0.0 {
+        format!("{:+}", delta as i64)
+    } else {
+        format!("{:+.2}", delta)
+    }
+}
+
+fn format_pct(a: f64, b: f64) -> String {
+    if a == 0.0 {
+        "-".to_string()
+    } else {
+        format!("{:+.2}%", (b - a) / a * 100.0)
+    }
+}
+
+fn read_fasta(path: &Path) -> Vec<(String, Vec<u8>)> {
+    let mut seqs: Vec<(String, Vec<u8>)> = vec![];
+
+    let reader = intspan::reader(path.to_str().unwrap());
+    for line in reader.lines().map_while(|r| r.ok()) {
+        if let Some(header) = line.strip_prefix('>') {
+            let name = header.split_whitespace().next().unwrap_or("").to_string();
+            seqs.push((name, vec![]));
+        } else if let Some(last) = seqs.last_mut() {
+            last.1.extend(line.trim_end().bytes());
+        }
+    }
+
+    seqs
+}
+
+// Canonical k-mers in 2-bit encoding, k-mers with non-ACGT bases are skipped
+fn kmers(seq: &[u8], k: usize) -> Vec<u64> {
+    let mask = (1u64 << (2 * k)) - 1;
+    let shift = 2 * (k - 1);
+
+    let mut result = vec![];
+    let (mut fwd, mut rev, mut valid) = (0u64, 0u64, 0usize);
+    for base in seq {
+        let code = match base {
+            b'A' | b'a' => 0,
+            b'C' | b'c' => 1,
+            b'G' | b'g' => 2,
+            b'T' | b't' => 3,
+            _ => {
+                valid = 0;
+                continue;
+            }
+        };
+        fwd = ((fwd << 2) | code) & mask;
+        rev = (rev >> 2) | ((3 - code) << shift);
+        valid += 1;
+        if valid >= k {
+            result.push(fwd.min(rev));
+        }
+    }
+
+    result
+}
+
+fn kmer_set(seqs: &[(String, Vec<u8>)], k: usize) -> HashSet<u64> {
+    seqs.iter().flat_map(|(_, seq)| kmers(seq, k)).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Class {
+    Shared = 0,
+    Discordant = 1,
+    Unique = 2,
+}
+
+struct Anchor {
+    name: String,
+    length: usize,
+    containment: f64,
+    class: Class,
+}
+
+// Proportion of k-mers of each anchor presenting in the other set
+fn classify(
+    seqs: &[(String, Vec<u8>)],
+    other: &HashSet<u64>,
+    k: usize,
+    shared: f64,
+    unique: f64,
+) -> Vec<Anchor> {
+    seqs.iter()
+        .map(|(name, seq)| {
+            let ks = kmers(seq, k);
+            let containment = if ks.is_empty() {
+                0.0
+            } else {
+                ks.iter().filter(|k| other.contains(k)).count() as f64 / ks.len() as f64
+            };
+            let class = if containment >= shared {
+                Class::Shared
+            } else if containment <= unique {
+                Class::Unique
+            } else {
+                Class::Discordant
+            };
+
+            Anchor {
+                name: name.clone(),
+                length: seq.len(),
+                containment,
+                class,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,41 @@
+use clap::*;
+
+// Create clap subcommand arguments
+pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("completions")
+        .about("Generates shell completions")
+        .after_help(
+            r#"
+* bash - source it in ~/.bashrc, or put it into /etc/bash_completion.d/
+* zsh  - save it as _anchr in a directory of $fpath
+* fish - save it as ~/.config/fish/completions/anchr.fish
+
+"#,
+        )
+        .arg(
+            Arg::with_name("shell")
+                .help("The shell")
+                .required(true)
+                .possible_values(&Shell::variants())
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("outfile")
+                .short("o")
+                .long("outfile")
+                .takes_value(true)
+                .default_value("stdout")
+                .empty_values(false)
+                .help("Output filename. [stdout] for screen"),
+        )
+}
+
+// command implementation
+pub fn execute(args: &ArgMatches, mut app: App) -> std::result::Result<(), std::io::Error> {
+    let shell = value_t!(args, "shell", Shell).unwrap_or_else(|e| e.exit());
+
+    let mut writer = intspan::writer(args.value_of("outfile").unwrap());
+    app.gen_completions_to("anchr", shell, &mut writer);
+
+    Ok(())
+}
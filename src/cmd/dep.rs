@@ -1,4 +1,6 @@
 use clap::*;
+use std::collections::HashMap;
+use tera::{Context, Tera};
 
 // Create clap subcommand arguments
 pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
@@ -8,6 +10,9 @@ pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
             r#"
 * check   - check dependencies
 * install - install dependencies
+
+With --container and --images, `check` validates that images exist locally
+
 "#,
         )
         .arg(
@@ -16,6 +21,23 @@ pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
                 .required(true)
                 .index(1),
         )
+        .arg(
+            Arg::with_name("container")
+                .long("container")
+                .help("Check images instead: docker, singularity, or apptainer")
+                .takes_value(true)
+                .possible_values(&["docker", "singularity", "apptainer"])
+                .requires("images")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("images")
+                .long("images")
+                .help("A TSV file of tool and image")
+                .takes_value(true)
+                .requires("container")
+                .empty_values(false),
+        )
         .arg(
             Arg::with_name("outfile")
                 .short("o")
@@ -31,6 +53,21 @@ pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
 pub fn execute(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
     let mut writer = intspan::writer(args.value_of("outfile").unwrap());
 
+    if args.is_present("container") {
+        let infile = args.value_of("infile").unwrap();
+        if infile != "check" {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("--container can't be used with {}", infile),
+            ));
+        }
+
+        let rendered = check_container(args);
+        writer.write_all(rendered.as_ref())?;
+
+        return Ok(());
+    }
+
     let kb = match args.value_of("infile").unwrap() {
         "check" => include_str!("../../templates/check_dep.sh"),
         "install" => include_str!("../../templates/install_dep.sh"),
@@ -41,3 +78,23 @@ pub fn execute(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
 
     Ok(())
 }
+
+fn check_container(args: &ArgMatches) -> String {
+    let mut opt = HashMap::new();
+    opt.insert("container", args.value_of("container").unwrap());
+
+    let images = anchr::libs::template::read_images(args.value_of("images").unwrap());
+
+    let mut context = Context::new();
+    context.insert("opt", &opt);
+    context.insert("images", &images);
+
+    let mut tera = Tera::default();
+    tera.add_raw_templates(vec![(
+        "t",
+        include_str!("../../templates/check_container.tera.sh"),
+    )])
+    .unwrap();
+
+    tera.render("t", &context).unwrap()
+}
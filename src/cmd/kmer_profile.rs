@@ -0,0 +1,313 @@
+use clap::*;
+use serde_json::json;
+use std::io::BufRead;
+
+// Create clap subcommand arguments
+pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("kmer-profile")
+        .about("Genome size, heterozygosity and repeats from a k-mer histogram")
+        .after_help(
+            r#"
+<infile> is a k-mer histogram, such as `khist` of kmercountexact.sh
+or the output of `jellyfish histo`
+    * Lines starting with # are skipped
+    * The first column is the depth and the last one is the number of distinct k-mers
+
+Four negative binomial peaks, at 1x, 2x, 3x and 4x of the haploid k-mer coverage,
+are fitted to the histogram beyond the trough of erroneous k-mers
+    * 1x: heterozygous k-mers
+    * 2x: homozygous k-mers, the main peak
+    * 3x and 4x: duplications
+    * The main peak is taken as heterozygous when the fitted 4x peak is
+      larger than half of the 2x one
+
+The output is a JSON object
+    * GENOME_SIZE, total non-erroneous k-mers / the homozygous coverage
+    * HETEROZYGOSITY, per base
+    * REPEAT_FRACTION, the part of genome not covered by unique k-mers
+    * ERROR_FRACTION, k-mers below the trough
+
+"#,
+        )
+        .arg(
+            Arg::with_name("infile")
+                .help("Sets the input file to use")
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("kmer")
+                .long("kmer")
+                .short("k")
+                .help("The k-mer size of the histogram")
+                .takes_value(true)
+                .default_value("31")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("outfile")
+                .short("o")
+                .long("outfile")
+                .takes_value(true)
+                .default_value("stdout")
+                .empty_values(false)
+                .help("Output filename. [stdout] for screen"),
+        )
+}
+
+// command implementation
+pub fn execute(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
+    let kmer = value_t!(args.value_of("kmer"), usize).unwrap_or_else(|e| {
+        eprintln!("Need an integer for --kmer\n{}", e);
+        std::process::exit(1)
+    });
+
+    let hist = read_hist(args.value_of("infile").unwrap());
+    let profile = match fit_profile(&hist, kmer) {
+        Some(p) => p,
+        None => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Can't find a peak beyond erroneous k-mers",
+            ))
+        }
+    };
+
+    let env = json!({
+        "KMER": kmer,
+        "ERROR_THRESHOLD": profile.threshold,
+        "KMER_COVERAGE": round(profile.coverage, 2),
+        "GENOME_SIZE": profile.genome_size,
+        "HETEROZYGOSITY": round(profile.heterozygosity, 6),
+        "REPEAT_FRACTION": round(profile.repeat_fraction, 4),
+        "ERROR_FRACTION": round(profile.error_fraction, 4),
+    });
+
+    let mut writer = intspan::writer(args.value_of("outfile").unwrap());
+    writer.write_all((serde_json::to_string_pretty(&env).unwrap() + "\n").as_ref())?;
+
+    Ok(())
+}
+
+#[derive(Default, Debug)]
+struct Profile {
+    threshold: usize,
+    coverage: f64,
+    genome_size: u64,
+    heterozygosity: f64,
+    repeat_fraction: f64,
+    error_fraction: f64,
+}
+
+// hist[depth] = number of distinct k-mers
+pub fn read_hist(infile: &str) -> Vec<f64> {
+    let mut hist: Vec<f64> = vec![];
+
+    let reader = intspan::reader(infile);
+    for line in reader.lines().map_while(|r| r.ok()) {
+        if line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 2 {
+            continue;
+        }
+        let depth: usize = match fields[0].parse() {
+            Ok(d) => d,
+            Err(_) => continue,
+        };
+        let count: f64 = match fields.last().unwrap().parse() {
+            Ok(c) if f64::is_finite(c) => c,
+            _ => continue,
+        };
+
+        if hist.len() <= depth {
+            hist.resize(depth + 1, 0.0);
+        }
+        hist[depth] = count;
+    }
+
+    hist
+}
+
+fn fit_profile(hist: &[f64], kmer: usize) -> Option<Profile> {
+    // The trough between erroneous k-mers and the first peak
+    let threshold = (1..hist.len().saturating_sub(1)).find(|&d| hist[d + 1] > hist[d])?;
+
+    // The main peak
+    let peak = (threshold..hist.len()).max_by(|&a, &b| hist[a].total_cmp(&hist[b]))?;
+    if peak <= threshold {
+        return None;
+    }
+
+    // The main peak is homozygous, or heterozygous when the "duplications"
+    // at twice the main peak are more than half of it
+    let (mut lambda, mut amp) = fit_peaks(hist, threshold, peak as f64 * 0.35, peak as f64 * 0.65)?;
+    if amp[3] > amp[1] * 0.5 {
+        let (l, a) = fit_peaks(hist, threshold, peak as f64 * 0.85, peak as f64 * 1.15)?;
+        lambda = l;
+        amp = a;
+    }
+
+    let all: f64 = hist.iter().enumerate().map(|(d, c)| d as f64 * c).sum();
+    let solid: f64 = hist
+        .iter()
+        .enumerate()
+        .skip(threshold)
+        .map(|(d, c)| d as f64 * c)
+        .sum();
+
+    let coverage = lambda * 2.0;
+    let genome_size = (solid / coverage).round();
+
+    // A diploid genome of length G with heterozygosity r has
+    // 2G(1 - (1 - r)^k) heterozygous k-mers and G(1 - r)^k homozygous ones
+    let (het, hom) = (amp[0], amp[1]);
+    let heterozygosity = if het > 0.0 && hom > 0.0 {
+        1.0 - (2.0 * hom / (het + 2.0 * hom)).powf(1.0 / kmer as f64)
+    } else {
+        0.0
+    };
+    let unique = hom + het / 2.0;
+    let repeat_fraction = (1.0 - unique / genome_size).max(0.0);
+
+    Some(Profile {
+        threshold,
+        coverage,
+        genome_size: genome_size as u64,
+        heterozygosity,
+        repeat_fraction,
+        error_fraction: if all > 0.0 { (all - solid) / all } else { 0.0 },
+    })
+}
+
+// Grid search on the haploid coverage and the dispersion
+// Amplitudes of the four peaks are fitted by non-negative least squares
+fn fit_peaks(hist: &[f64], threshold: usize, lower: f64, upper: f64) -> Option<(f64, [f64; 4])> {
+    let max = ((upper * 5.0).ceil() as usize).min(hist.len() - 1);
+    if max <= threshold {
+        return None;
+    }
+    let observed: Vec<f64> = hist[threshold..=max].to_vec();
+
+    let mut best: Option<(f64, f64, [f64; 4])> = None;
+    let steps = 60;
+    for i in 0..=steps {
+        let lambda = lower + (upper - lower) * i as f64 / steps as f64;
+        for size in &[f64::INFINITY, 100.0, 50.0, 20.0, 10.0, 5.0] {
+            let columns: Vec<Vec<f64>> = (1..=4)
+                .map(|j| {
+                    let pmf = nb_pmf(lambda * j as f64, size * j as f64, max);
+                    pmf[threshold..=max].to_vec()
+                })
+                .collect();
+
+            let (amp, sse) = nnls(&columns, &observed);
+            if best.is_none_or(|b| sse < b.1) {
+                best = Some((lambda, sse, amp));
+            }
+        }
+    }
+
+    best.map(|(lambda, _, amp)| (lambda, amp))
+}
+
+// Negative binomial with the given mean and size, Poisson when size is infinite
+// Computed in log space for high coverages
+fn nb_pmf(mean: f64, size: f64, max: usize) -> Vec<f64> {
+    let mut pmf = Vec::with_capacity(max + 1);
+
+    let mut log_p = if size.is_infinite() {
+        -mean
+    } else {
+        size * (size / (size + mean)).ln()
+    };
+    pmf.push(log_p.exp());
+    for x in 1..=max {
+        log_p += if size.is_infinite() {
+            (mean / x as f64).ln()
+        } else {
+            ((x as f64 - 1.0 + size) / x as f64).ln() + (mean / (size + mean)).ln()
+        };
+        pmf.push(log_p.exp());
+    }
+
+    pmf
+}
+
+// Exhaustive non-negative least squares, fine for a handful of columns
+fn nnls(columns: &[Vec<f64>], observed: &[f64]) -> ([f64; 4], f64) {
+    let n = columns.len();
+    let mut best = ([0.0; 4], observed.iter().map(|y| y * y).sum::<f64>());
+
+    for mask in 1..(1usize << n) {
+        let idx: Vec<usize> = (0..n).filter(|i| mask & (1 << i) != 0).collect();
+        let m = idx.len();
+
+        // Normal equations
+        let mut a = vec![vec![0.0; m + 1]; m];
+        for (r, &i) in idx.iter().enumerate() {
+            for (c, &j) in idx.iter().enumerate() {
+                a[r][c] = dot(&columns[i], &columns[j]);
+            }
+            a[r][m] = dot(&columns[i], observed);
+        }
+        let x = match solve(a) {
+            Some(x) => x,
+            None => continue,
+        };
+        if x.iter().any(|v| *v < 0.0) {
+            continue;
+        }
+
+        let mut amp = [0.0; 4];
+        for (r, &i) in idx.iter().enumerate() {
+            amp[i] = x[r];
+        }
+        let sse: f64 = observed
+            .iter()
+            .enumerate()
+            .map(|(d, y)| {
+                let fit: f64 = (0..n).map(|i| amp[i] * columns[i][d]).sum();
+                (y - fit).powi(2)
+            })
+            .sum();
+        if sse < best.1 {
+            best = (amp, sse);
+        }
+    }
+
+    best
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+// Gaussian elimination on an augmented matrix
+fn solve(mut a: Vec<Vec<f64>>) -> Option<Vec<f64>> {
+    let n = a.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        for row in 0..n {
+            if row != col {
+                let factor = a[row][col] / a[col][col];
+                let pivot_row = a[col].clone();
+                for (x, p) in a[row].iter_mut().zip(pivot_row).skip(col) {
+                    *x -= factor * p;
+                }
+            }
+        }
+    }
+
+    Some((0..n).map(|i| a[i][n] / a[i][i]).collect())
+}
+
+fn round(value: f64, digits: i32) -> f64 {
+    let scale = 10f64.powi(digits);
+    (value * scale).round() / scale
+}
@@ -0,0 +1,129 @@
+use clap::*;
+
+// Create clap subcommand arguments
+pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("man")
+        .about("Generates the man page")
+        .after_help(
+            r#"
+Sections of the man page are built from the help of all subcommands, nested ones included
+
+    anchr man -o anchr.1
+    man ./anchr.1
+
+"#,
+        )
+        .arg(
+            Arg::with_name("outfile")
+                .short("o")
+                .long("outfile")
+                .takes_value(true)
+                .default_value("stdout")
+                .empty_values(false)
+                .help("Output filename. [stdout] for screen"),
+        )
+}
+
+// command implementation
+pub fn execute(
+    args: &ArgMatches,
+    app: App,
+    subcommands: Vec<App>,
+) -> std::result::Result<(), std::io::Error> {
+    let mut writer = intspan::writer(args.value_of("outfile").unwrap());
+
+    let mut page = String::new();
+    page += &format!(
+        ".TH ANCHR 1 \"\" \"anchr {}\" \"User Commands\"\n",
+        crate_version!()
+    );
+    page += ".SH NAME\nanchr \\- Assembler of N\\-free CHRomosomes\n";
+    page += ".SH SYNOPSIS\n\\fBanchr\\fR <SUBCOMMAND> [OPTIONS]\n";
+    page += ".SH DESCRIPTION\n";
+    page += &help_to_roff(&long_help(app)?);
+
+    for sub in subcommands {
+        page += &section(sub, "anchr")?;
+    }
+
+    page += ".SH AUTHOR\n";
+    page += &format!("{}\n", escape(crate_authors!()));
+
+    writer.write_all(page.as_ref())?;
+
+    Ok(())
+}
+
+// A section for the subcommand, followed by ones of its nested subcommands like `ANCHR READS FILTER`
+fn section(sub: App, parent: &str) -> std::result::Result<String, std::io::Error> {
+    let bin_name = format!("{} {}", parent, sub.get_name());
+    let nested = sub.p.subcommands.clone();
+
+    let mut roff = format!(".SH \"{}\"\n", bin_name.to_uppercase());
+    roff += &help_to_roff(&long_help(sub.bin_name(bin_name.as_str()))?);
+    for n in nested {
+        roff += &section(n, &bin_name)?;
+    }
+
+    Ok(roff)
+}
+
+fn long_help(mut app: App) -> std::result::Result<String, std::io::Error> {
+    let mut buf: Vec<u8> = vec![];
+    app.write_long_help(&mut buf)
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+
+    Ok(String::from_utf8_lossy(&buf).to_string())
+}
+
+// Headers like `OPTIONS:` and grouped sections of after_help like `* Info` become
+// subsections, everything else is kept as is
+fn help_to_roff(help: &str) -> String {
+    let lines: Vec<&str> = help.lines().collect();
+
+    // Skip the name and version line
+    let mut roff = String::from(".nf\n");
+    for (i, line) in lines.iter().enumerate().skip(1) {
+        // Followed by indented items
+        let grouped = lines[i + 1..]
+            .iter()
+            .take(2)
+            .find(|l| !l.trim().is_empty())
+            .is_some_and(|l| l.starts_with(' '));
+        let is_header = line.ends_with(':')
+            && !line.starts_with(' ')
+            && line.chars().all(|c| c.is_ascii_uppercase() || c == ':');
+
+        let heading = if is_header {
+            Some(line.trim_end_matches(':'))
+        } else if grouped {
+            line.strip_prefix("* ")
+        } else {
+            None
+        };
+
+        match heading {
+            Some(heading) => roff += &format!(".fi\n.SS {}\n.nf\n", escape(heading)),
+            None if line.trim().is_empty() => roff += "\n",
+            None => roff += &format!("{}\n", escape(line.trim_end())),
+        }
+    }
+    roff += ".fi\n";
+
+    // Remove extra blank lines and empty blocks
+    while roff.contains("\n\n\n") {
+        roff = roff.replace("\n\n\n", "\n\n");
+    }
+    roff.replace("\n\n.fi\n", "\n.fi\n")
+        .replace(".nf\n\n", ".nf\n")
+        .replace(".nf\n.fi\n", "")
+}
+
+fn escape(line: &str) -> String {
+    let line = line.replace('\\', "\\e").replace('-', "\\-");
+    if line.starts_with('.') || line.starts_with('\'') {
+        format!("\\&{}", line)
+    } else {
+        line
+    }
+}
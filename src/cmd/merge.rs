@@ -1,7 +1,6 @@
+use anchr::MergeOptions;
 use clap::*;
 use itertools::Itertools;
-use std::collections::HashMap;
-use tera::{Context, Tera};
 
 // Create clap subcommand arguments
 pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
@@ -103,52 +102,18 @@ Rs is single
 pub fn execute(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
     let mut writer = intspan::writer(args.value_of("outfile").unwrap());
 
-    // context from args
-    let mut opt = HashMap::new();
-    opt.insert("len", args.value_of("len").unwrap());
-    opt.insert("qual", args.value_of("qual").unwrap());
-    opt.insert(
-        "prefilter",
-        if args.is_present("prefilter") {
-            args.value_of("prefilter").unwrap()
-        } else {
-            "0"
-        },
-    );
-    opt.insert("ecphase", args.value_of("ecphase").unwrap());
-    opt.insert("prefixm", args.value_of("prefixm").unwrap());
-    opt.insert("prefixu", args.value_of("prefixu").unwrap());
-    opt.insert(
-        "xmx",
-        if args.is_present("xmx") {
-            args.value_of("xmx").unwrap()
-        } else {
-            "0"
-        },
-    );
-    opt.insert("parallel", args.value_of("parallel").unwrap());
+    // options from args
+    let mut opt = MergeOptions::new(&args.values_of("infiles").unwrap().collect_vec());
+    opt.len = value_t!(args, "len", usize).unwrap_or_else(|e| e.exit());
+    opt.qual = value_t!(args, "qual", usize).unwrap_or_else(|e| e.exit());
+    opt.prefilter = args.value_of("prefilter").map(|s| s.to_string());
+    opt.ecphase = args.value_of("ecphase").unwrap().to_string();
+    opt.prefixm = args.value_of("prefixm").unwrap().to_string();
+    opt.prefixu = args.value_of("prefixu").unwrap().to_string();
+    opt.xmx = args.value_of("xmx").map(|s| s.to_string());
+    opt.parallel = value_t!(args, "parallel", usize).unwrap_or_else(|e| e.exit());
 
-    let infiles = args.values_of("infiles").unwrap().collect_vec();
-
-    let mut context = Context::new();
-    context.insert("opt", &opt);
-    context.insert("args", &infiles);
-
-    // eprintln!("{:#?}", context);
-
-    // many templates
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("merge", include_str!("../../templates/merge.tera.sh")),
-    ])
-    .unwrap();
-
-    // eprintln!("{:#?}", tera);
-
-    let rendered = tera.render("merge", &context).unwrap();
-
-    writer.write_all(rendered.as_ref())?;
+    writer.write_all(opt.render()?.as_ref())?;
 
     Ok(())
 }
@@ -1,10 +1,19 @@
 //! Subcommand modules for the `anchr` binary.
 
 pub mod anchors;
+pub mod batch;
+pub mod compare;
+pub mod completions;
 pub mod dep;
 pub mod ena;
+pub mod kmer_profile;
+pub mod man;
 pub mod merge;
+pub mod qtrim;
 pub mod quorum;
+pub mod reads;
+pub mod report;
+pub mod select;
 pub mod template;
 pub mod trim;
 pub mod unitigs;
@@ -0,0 +1,374 @@
+use anchr::libs::fastx::{Reader, Record, Writer};
+use clap::*;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+// Create clap subcommand arguments
+pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("qtrim")
+        .about("Quality and length trimming of all Q/L combinations in one pass")
+        .after_help(
+            r#"
+<R1> [R2]
+
+Fastq files can be gzipped, and outputs are always gzipped
+
+Sliding-window trimming like sickle
+    * The window is 10% of the read length
+    * The 5' end is cut at the first base above --qual in the first good window
+    * The 3' end is cut at the first base below --qual in the first bad window
+    * Trimmed reads shorter than --len are discarded
+
+Outputs of each combination are in <outdir>/Q{qual}L{len}/
+    * PE: <prefix>1.fq.gz, <prefix>2.fq.gz, and <prefix>s.fq.gz for reads
+      whose mates are discarded, plus trimmed reads of --single
+    * SE: <prefix>1.fq.gz
+    * Combinations with an existing <prefix>1.fq.gz are skipped
+
+"#,
+        )
+        .arg(
+            Arg::with_name("infiles")
+                .help("Sets the input file to use")
+                .required(true)
+                .min_values(1)
+                .max_values(2)
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("single")
+                .long("single")
+                .help("Single reads besides pairs")
+                .takes_value(true)
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("qual")
+                .long("qual")
+                .short("q")
+                .help("Quality thresholds, space separated")
+                .takes_value(true)
+                .default_value("25 30")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("len")
+                .long("len")
+                .short("l")
+                .help("Length thresholds, space separated")
+                .takes_value(true)
+                .default_value("60")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("prefix")
+                .long("prefix")
+                .help("Prefix of output files")
+                .takes_value(true)
+                .default_value("R")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("outdir")
+                .long("outdir")
+                .short("o")
+                .help("Output directory")
+                .takes_value(true)
+                .default_value(".")
+                .empty_values(false),
+        )
+}
+
+// command implementation
+pub fn execute(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
+    let infiles: Vec<&str> = args.values_of("infiles").unwrap().collect();
+    let is_pe = infiles.len() == 2;
+    let prefix = args.value_of("prefix").unwrap();
+    let outdir = Path::new(args.value_of("outdir").unwrap());
+
+    if args.is_present("single") && !is_pe {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "--single needs paired files",
+        ));
+    }
+
+    let quals = parse_numbers::<u8>(args.value_of("qual").unwrap(), "--qual")?;
+    let lens = parse_numbers::<usize>(args.value_of("len").unwrap(), "--len")?;
+
+    // Open inputs before creating any outputs
+    let mut reader1 = Reader::new(infiles[0])?;
+    let mut reader2 = infiles.get(1).map(|f| Reader::new(f)).transpose()?;
+    let mut reader_s = args.value_of("single").map(Reader::new).transpose()?;
+
+    //----------------------------
+    // Combinations to be created
+    //----------------------------
+    let mut combos = vec![];
+    for (qi, qual) in quals.iter().enumerate() {
+        for len in &lens {
+            let dir = outdir.join(format!("Q{}L{}", qual, len));
+            eprintln!("==> Qual-Len: Q{}L{}", qual, len);
+            if dir.join(format!("{}1.fq.gz", prefix)).exists() {
+                eprintln!("    {}1.fq.gz already presents", prefix);
+                continue;
+            }
+            combos.push(Combo::new(qi, *len, &dir, prefix, is_pe)?);
+        }
+    }
+    if combos.is_empty() {
+        return Ok(());
+    }
+
+    // Outputs are written to .tmp files, and renamed only when all of them are finished.
+    // So a failed or killed run leaves nothing to be skipped by the next one
+    let result = trim_all(
+        &mut reader1,
+        reader2.as_mut(),
+        reader_s.as_mut(),
+        &infiles,
+        &quals,
+        &mut combos,
+    );
+    let result = result.and_then(|_| {
+        for combo in combos.iter_mut() {
+            for writer in combo.writers.drain(..) {
+                writer.finish()?;
+            }
+        }
+        Ok(())
+    });
+    if let Err(e) = result {
+        for combo in &combos {
+            for (tmp, _) in &combo.files {
+                std::fs::remove_file(tmp).ok();
+            }
+        }
+        return Err(e);
+    }
+
+    for combo in combos {
+        if is_pe {
+            eprintln!(
+                "{}: {} pairs, {} singles",
+                combo.dir.display(),
+                combo.pairs,
+                combo.singles
+            );
+        } else {
+            eprintln!("{}: {} reads", combo.dir.display(), combo.singles);
+        }
+        // <prefix>1.fq.gz is the last one, as it marks the combination as done
+        for (tmp, file) in combo.files.iter().rev() {
+            std::fs::rename(tmp, file)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn trim_all(
+    reader1: &mut Reader,
+    mut reader2: Option<&mut Reader>,
+    reader_s: Option<&mut Reader>,
+    infiles: &[&str],
+    quals: &[u8],
+    combos: &mut [Combo],
+) -> std::result::Result<(), std::io::Error> {
+    //----------------------------
+    // Pairs, or single-end reads
+    //----------------------------
+    while let Some(rec1) = reader1.read()? {
+        let ranges1 = trimmed_ranges(&rec1, infiles[0], quals)?;
+
+        match reader2.as_mut() {
+            Some(reader2) => {
+                let rec2 = reader2.read()?.ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("{} has fewer reads than {}", infiles[1], infiles[0]),
+                    )
+                })?;
+                let ranges2 = trimmed_ranges(&rec2, infiles[1], quals)?;
+
+                for combo in combos.iter_mut() {
+                    let r1 = ranges1[combo.qi].clone();
+                    let r2 = ranges2[combo.qi].clone();
+                    match (combo.keep(&r1), combo.keep(&r2)) {
+                        (true, true) => {
+                            rec1.write_range(&mut combo.writers[0], false, r1)?;
+                            rec2.write_range(&mut combo.writers[1], false, r2)?;
+                            combo.pairs += 1;
+                        }
+                        (true, false) => {
+                            rec1.write_range(&mut combo.writers[2], false, r1)?;
+                            combo.singles += 1;
+                        }
+                        (false, true) => {
+                            rec2.write_range(&mut combo.writers[2], false, r2)?;
+                            combo.singles += 1;
+                        }
+                        (false, false) => {}
+                    }
+                }
+            }
+            None => {
+                for combo in combos.iter_mut() {
+                    let r1 = ranges1[combo.qi].clone();
+                    if combo.keep(&r1) {
+                        rec1.write_range(&mut combo.writers[0], false, r1)?;
+                        combo.singles += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(reader2) = reader2 {
+        if reader2.read()?.is_some() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} has fewer reads than {}", infiles[0], infiles[1]),
+            ));
+        }
+    }
+
+    //----------------------------
+    // Single reads of PE
+    //----------------------------
+    if let Some(reader) = reader_s {
+        while let Some(rec) = reader.read()? {
+            let ranges = trimmed_ranges(&rec, "--single", quals)?;
+            for combo in combos.iter_mut() {
+                let r = ranges[combo.qi].clone();
+                if combo.keep(&r) {
+                    rec.write_range(&mut combo.writers[2], false, r)?;
+                    combo.singles += 1;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Kept ranges of each quality threshold
+fn trimmed_ranges(
+    rec: &Record,
+    infile: &str,
+    quals: &[u8],
+) -> std::result::Result<Vec<Range<usize>>, std::io::Error> {
+    let qual = rec.qual.as_ref().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{} is not FASTQ", infile),
+        )
+    })?;
+
+    Ok(quals.iter().map(|q| sliding_window(qual, *q)).collect())
+}
+
+fn parse_numbers<T: std::str::FromStr>(
+    s: &str,
+    name: &str,
+) -> std::result::Result<Vec<T>, std::io::Error> {
+    s.split_ascii_whitespace()
+        .map(|v| {
+            v.parse::<T>().map_err(|_| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Invalid {} {}", name, v),
+                )
+            })
+        })
+        .collect()
+}
+
+struct Combo {
+    // index of the quality threshold
+    qi: usize,
+    len: usize,
+    dir: PathBuf,
+    // .tmp and final names of 1, 2 and s
+    files: Vec<(PathBuf, PathBuf)>,
+    writers: Vec<Writer>,
+    pairs: usize,
+    singles: usize,
+}
+
+impl Combo {
+    fn new(
+        qi: usize,
+        len: usize,
+        dir: &Path,
+        prefix: &str,
+        is_pe: bool,
+    ) -> std::result::Result<Self, std::io::Error> {
+        std::fs::create_dir_all(dir)?;
+
+        let suffixes: &[&str] = if is_pe { &["1", "2", "s"] } else { &["1"] };
+        let mut files = vec![];
+        let mut writers = vec![];
+        for suffix in suffixes {
+            let file = dir.join(format!("{}{}.fq.gz", prefix, suffix));
+            let tmp = dir.join(format!("{}{}.fq.gz.tmp", prefix, suffix));
+            writers.push(Writer::gz(&tmp)?);
+            files.push((tmp, file));
+        }
+
+        Ok(Combo {
+            qi,
+            len,
+            dir: dir.to_path_buf(),
+            files,
+            writers,
+            pairs: 0,
+            singles: 0,
+        })
+    }
+
+    fn keep(&self, range: &Range<usize>) -> bool {
+        !range.is_empty() && range.len() >= self.len
+    }
+}
+
+// Sanger (phred+33) qualities, returns the kept range
+fn sliding_window(qual: &[u8], threshold: u8) -> Range<usize> {
+    let len = qual.len();
+    if len == 0 {
+        return 0..0;
+    }
+    let q = |i: usize| qual[i].saturating_sub(33) as usize;
+    let threshold = threshold as usize;
+
+    let window = std::cmp::max(len / 10, 1);
+    let mut total: usize = (0..window).map(q).sum();
+
+    let mut five_prime: Option<usize> = None;
+    let mut three_prime = len;
+    for start in 0..=(len - window) {
+        let is_good = total >= threshold * window;
+
+        // the first base above the threshold in the first good window
+        if five_prime.is_none() && is_good {
+            five_prime = (start..start + window).find(|&i| q(i) >= threshold);
+        }
+
+        // the first base below the threshold in the first bad window after the 5' end
+        if five_prime.is_some() && !is_good {
+            three_prime = (start..start + window)
+                .find(|&i| q(i) < threshold)
+                .unwrap_or(start);
+            break;
+        }
+
+        if start + window < len {
+            total = total + q(start + window) - q(start);
+        }
+    }
+
+    match five_prime {
+        Some(five_prime) if five_prime < three_prime => five_prime..three_prime,
+        _ => 0..0,
+    }
+}
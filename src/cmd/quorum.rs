@@ -1,7 +1,6 @@
+use anchr::QuorumOptions;
 use clap::*;
 use itertools::Itertools;
-use std::collections::HashMap;
-use tera::{Context, Tera};
 
 // Create clap subcommand arguments
 pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
@@ -69,34 +68,14 @@ Fastq files can be gzipped
 pub fn execute(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
     let mut writer = intspan::writer(args.value_of("outfile").unwrap());
 
-    // context from args
-    let mut opt = HashMap::new();
-    opt.insert("jf", args.value_of("jf").unwrap());
-    opt.insert("estsize", args.value_of("estsize").unwrap());
-    opt.insert("prefix", args.value_of("prefix").unwrap());
-    opt.insert("parallel", args.value_of("parallel").unwrap());
+    // options from args
+    let mut opt = QuorumOptions::new(&args.values_of("infiles").unwrap().collect_vec());
+    opt.jf = value_t!(args, "jf", usize).unwrap_or_else(|e| e.exit());
+    opt.estsize = args.value_of("estsize").unwrap().to_string();
+    opt.prefix = args.value_of("prefix").unwrap().to_string();
+    opt.parallel = value_t!(args, "parallel", usize).unwrap_or_else(|e| e.exit());
 
-    let infiles = args.values_of("infiles").unwrap().collect_vec();
-
-    let mut context = Context::new();
-    context.insert("opt", &opt);
-    context.insert("args", &infiles);
-
-    // eprintln!("{:#?}", context);
-
-    // many templates
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("quorum", include_str!("../../templates/quorum.tera.sh")),
-    ])
-    .unwrap();
-
-    // eprintln!("{:#?}", tera);
-
-    let rendered = tera.render("quorum", &context).unwrap();
-
-    writer.write_all(rendered.as_ref())?;
+    writer.write_all(opt.render()?.as_ref())?;
 
     Ok(())
 }
@@ -0,0 +1,359 @@
+use anchr::libs::fastx::{Reader, Record, Writer};
+use clap::*;
+use std::collections::HashMap;
+
+// Create clap subcommand arguments
+pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("reads")
+        .about("Filters, interleaves and re-pairs reads")
+        .after_help(
+            r#"
+* filter       - keep reads by length
+* interleave   - interleave R1 and R2, or pad single reads with N mates
+* deinterleave - split interleaved reads into R1 and R2
+* repair       - re-pair reads by names, orphans are written to --outs
+
+Inputs are FASTA or FASTQ, and can be gzipped. [stdin] for standard input
+
+Outputs keep the format of inputs, or are FASTA with --fasta.
+Output files ending in .gz are gzipped. [stdout] for screen
+
+"#,
+        )
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("filter")
+                .about("Keeps reads by length")
+                .arg(
+                    Arg::with_name("infiles")
+                        .help("Sets the input files to use")
+                        .required(true)
+                        .min_values(1)
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("minlen")
+                        .long("minlen")
+                        .short("a")
+                        .help("Minimal length of reads")
+                        .takes_value(true)
+                        .default_value("1")
+                        .empty_values(false),
+                )
+                .arg(
+                    Arg::with_name("maxlen")
+                        .long("maxlen")
+                        .short("z")
+                        .help("Maximal length of reads, 0 for unlimited")
+                        .takes_value(true)
+                        .default_value("0")
+                        .empty_values(false),
+                )
+                .arg(Arg::with_name("fasta").long("fasta").help("Writes FASTA"))
+                .arg(
+                    Arg::with_name("outfile")
+                        .short("o")
+                        .long("outfile")
+                        .takes_value(true)
+                        .default_value("stdout")
+                        .empty_values(false)
+                        .help("Output filename. [stdout] for screen"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("interleave")
+                .about("Interleaves paired reads")
+                .after_help(
+                    r#"
+Reads are renamed to <prefix>_<index>/1 and <prefix>_<index>/2
+
+With only one file, each read is followed by a mate of a single N
+
+"#,
+                )
+                .arg(
+                    Arg::with_name("infiles")
+                        .help("Sets the input files to use")
+                        .required(true)
+                        .min_values(1)
+                        .max_values(2)
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("prefix")
+                        .long("prefix")
+                        .short("p")
+                        .help("Prefix of read names")
+                        .takes_value(true)
+                        .default_value("read")
+                        .empty_values(false),
+                )
+                .arg(Arg::with_name("fasta").long("fasta").help("Writes FASTA"))
+                .arg(
+                    Arg::with_name("outfile")
+                        .short("o")
+                        .long("outfile")
+                        .takes_value(true)
+                        .default_value("stdout")
+                        .empty_values(false)
+                        .help("Output filename. [stdout] for screen"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("deinterleave")
+                .about("Splits interleaved reads")
+                .arg(
+                    Arg::with_name("infile")
+                        .help("Sets the input file to use")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(Arg::with_name("fasta").long("fasta").help("Writes FASTA"))
+                .arg(
+                    Arg::with_name("out1")
+                        .long("out1")
+                        .help("Output filename of the first reads")
+                        .takes_value(true)
+                        .required(true)
+                        .empty_values(false),
+                )
+                .arg(
+                    Arg::with_name("out2")
+                        .long("out2")
+                        .help("Output filename of the second reads")
+                        .takes_value(true)
+                        .required(true)
+                        .empty_values(false),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("repair")
+                .about("Re-pairs reads by names")
+                .after_help(
+                    r#"
+Mates share the name before the first whitespace, with or without /1 and /2.
+Reads are paired wherever their mates are, such as after `anchr reads filter`
+dropping one of them.
+
+Without --out2, pairs are interleaved in --out1.
+Without --outs, orphans are discarded.
+
+"#,
+                )
+                .arg(
+                    Arg::with_name("infiles")
+                        .help("Sets the input files to use")
+                        .required(true)
+                        .min_values(1)
+                        .index(1),
+                )
+                .arg(Arg::with_name("fasta").long("fasta").help("Writes FASTA"))
+                .arg(
+                    Arg::with_name("out1")
+                        .long("out1")
+                        .help("Output filename of the first reads")
+                        .takes_value(true)
+                        .required(true)
+                        .empty_values(false),
+                )
+                .arg(
+                    Arg::with_name("out2")
+                        .long("out2")
+                        .help("Output filename of the second reads")
+                        .takes_value(true)
+                        .empty_values(false),
+                )
+                .arg(
+                    Arg::with_name("outs")
+                        .long("outs")
+                        .help("Output filename of orphans")
+                        .takes_value(true)
+                        .empty_values(false),
+                ),
+        )
+}
+
+// command implementation
+pub fn execute(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
+    match args.subcommand() {
+        ("filter", Some(sub)) => filter(sub),
+        ("interleave", Some(sub)) => interleave(sub),
+        ("deinterleave", Some(sub)) => deinterleave(sub),
+        ("repair", Some(sub)) => repair(sub),
+        _ => unreachable!(),
+    }
+}
+
+fn filter(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
+    let minlen = parse_number(args.value_of("minlen").unwrap(), "--minlen")?;
+    let maxlen = parse_number(args.value_of("maxlen").unwrap(), "--maxlen")?;
+    let fasta = args.is_present("fasta");
+    let mut writer = Writer::new(args.value_of("outfile").unwrap())?;
+
+    for infile in args.values_of("infiles").unwrap() {
+        let mut reader = Reader::new(infile)?;
+        while let Some(rec) = reader.read()? {
+            let len = rec.seq.len();
+            if len >= minlen && (maxlen == 0 || len <= maxlen) {
+                rec.write(&mut writer, fasta)?;
+            }
+        }
+    }
+    writer.finish()?;
+
+    Ok(())
+}
+
+fn interleave(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
+    let infiles: Vec<&str> = args.values_of("infiles").unwrap().collect();
+    let prefix = args.value_of("prefix").unwrap();
+    let fasta = args.is_present("fasta");
+    let mut writer = Writer::new(args.value_of("outfile").unwrap())?;
+
+    let mut reader1 = Reader::new(infiles[0])?;
+    let mut reader2 = infiles.get(1).map(|f| Reader::new(f)).transpose()?;
+
+    let mut index = 0;
+    while let Some(mut rec1) = reader1.read()? {
+        let mut rec2 = match reader2.as_mut() {
+            Some(reader2) => reader2.read()?.ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{} has fewer reads than {}", infiles[1], infiles[0]),
+                )
+            })?,
+            None => Record {
+                name: String::new(),
+                seq: b"N".to_vec(),
+                qual: rec1.qual.as_ref().map(|_| b"!".to_vec()),
+            },
+        };
+
+        rec1.name = format!("{}_{}/1", prefix, index);
+        rec2.name = format!("{}_{}/2", prefix, index);
+        rec1.write(&mut writer, fasta)?;
+        rec2.write(&mut writer, fasta)?;
+        index += 1;
+    }
+
+    if let Some(reader2) = reader2.as_mut() {
+        if reader2.read()?.is_some() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} has fewer reads than {}", infiles[0], infiles[1]),
+            ));
+        }
+    }
+    writer.finish()?;
+
+    Ok(())
+}
+
+fn deinterleave(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
+    let infile = args.value_of("infile").unwrap();
+    let fasta = args.is_present("fasta");
+    let mut writer1 = Writer::new(args.value_of("out1").unwrap())?;
+    let mut writer2 = Writer::new(args.value_of("out2").unwrap())?;
+
+    let mut reader = Reader::new(infile)?;
+    while let Some(rec1) = reader.read()? {
+        let rec2 = reader.read()?.ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} has no mate of {}", infile, rec1.name),
+            )
+        })?;
+        rec1.write(&mut writer1, fasta)?;
+        rec2.write(&mut writer2, fasta)?;
+    }
+    writer1.finish()?;
+    writer2.finish()?;
+
+    Ok(())
+}
+
+fn repair(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
+    let fasta = args.is_present("fasta");
+    let mut writer1 = Writer::new(args.value_of("out1").unwrap())?;
+    let mut writer2 = match args.value_of("out2") {
+        Some(outfile) => Some(Writer::new(outfile)?),
+        None => None,
+    };
+    let writers = match args.value_of("outs") {
+        Some(outfile) => Some(Writer::new(outfile)?),
+        None => None,
+    };
+
+    // Reads waiting for their mates, with the order they were read
+    let mut pending: HashMap<String, (usize, u8, Record)> = HashMap::new();
+    let mut orphans: Vec<(usize, Record)> = vec![];
+    let mut serial = 0;
+
+    for infile in args.values_of("infiles").unwrap() {
+        let mut reader = Reader::new(infile)?;
+        while let Some(rec) = reader.read()? {
+            let (key, mate) = pair_key(&rec.name);
+            let key = key.to_string();
+            serial += 1;
+
+            match pending.remove(&key) {
+                // Two first or two second reads, keeps the latter one waiting
+                Some((order, prev, prev_rec)) if prev != 0 && prev == mate => {
+                    orphans.push((order, prev_rec));
+                    pending.insert(key, (serial, mate, rec));
+                }
+                Some((_, prev, prev_rec)) => {
+                    let (rec1, rec2) = if prev == 2 || mate == 1 {
+                        (rec, prev_rec)
+                    } else {
+                        (prev_rec, rec)
+                    };
+                    rec1.write(&mut writer1, fasta)?;
+                    match writer2.as_mut() {
+                        Some(writer2) => rec2.write(writer2, fasta)?,
+                        None => rec2.write(&mut writer1, fasta)?,
+                    }
+                }
+                None => {
+                    pending.insert(key, (serial, mate, rec));
+                }
+            }
+        }
+    }
+
+    if let Some(mut writers) = writers {
+        orphans.extend(pending.into_iter().map(|(_, (order, _, rec))| (order, rec)));
+        orphans.sort_by_key(|(order, _)| *order);
+        for (_, rec) in orphans {
+            rec.write(&mut writers, fasta)?;
+        }
+        writers.finish()?;
+    }
+    writer1.finish()?;
+    if let Some(writer2) = writer2 {
+        writer2.finish()?;
+    }
+
+    Ok(())
+}
+
+// The name without /1 or /2, and which mate it is. 0 for unknown
+fn pair_key(name: &str) -> (&str, u8) {
+    let id = name.split_ascii_whitespace().next().unwrap_or("");
+    if let Some(key) = id.strip_suffix("/1") {
+        (key, 1)
+    } else if let Some(key) = id.strip_suffix("/2") {
+        (key, 2)
+    } else {
+        (id, 0)
+    }
+}
+
+fn parse_number(s: &str, name: &str) -> std::result::Result<usize, std::io::Error> {
+    s.parse::<usize>().map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Invalid {} {}", name, s),
+        )
+    })
+}
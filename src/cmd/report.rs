@@ -0,0 +1,1065 @@
+use clap::*;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::BufRead;
+use std::path::Path;
+use walkdir::WalkDir;
+
+// Create clap subcommand arguments
+pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("report")
+        .about("Reports of an assembly project")
+        .after_help(
+            r#"
+--resources
+    Tabulates wall time, CPU time and peak RSS of each stage
+    Records are written by scripts of `anchr template` into `resources/`
+    when GNU time (/usr/bin/time) is available
+
+--html
+    Gathers stat*.md and QUAST results of the project into one HTML file
+    Tables and SVG charts are embedded, no network access is needed
+    * k-mer histogram, 2_illumina/trim/R.khist.txt
+    * Insert sizes, 2_illumina/insert_size/R.ihist.*.txt
+    * Lengths of anchors, 7_merge_anchors/anchor.merge.fasta
+    * Base coverages of anchors with lower/upper cutoffs, */anchor/basecov.hist.tsv
+
+--table statFinal.tsv
+    Writes statFinal.json from the TSV table
+    Stats scripts of `anchr template` write raw numbers into a TSV next to
+    each Markdown table, and run it after that. Keys have their units
+    * `_bp` in base pairs, `_s` in seconds and `_pct` in percent
+    * `mapped_ratio` is a fraction, `cov_cor`, `median`, `mad`, `lower`
+      and `upper` are depths of coverage
+    * Empty cells are null
+    Columns of each table
+    * statReads, statFinal: name n50_bp sum_bp count
+      and qv completeness_pct mapped_pct of --merqury in statFinal
+    * statAnchors, statMRAnchors: name cov_cor mapped_ratio
+      n50_anchor_bp sum_anchor_bp count_anchor
+      n50_others_bp sum_others_bp count_others
+      median mad lower upper kmer runtime_ut_s runtime_an_s
+    * statMergeAnchors, statOtherAnchors: the same without
+      cov_cor, kmer and runtime_ut_s
+    * statKAT: k mean_freq est_genome_size_bp est_het_rate mean_gc_pct
+    * statPolish: name n50_bp sum_bp count changes
+    * statMerqury: name qv completeness_pct mapped_pct
+    * statMeta: name cov gc n50_bp sum_bp count
+      cov and gc are lower bounds of bins
+    * statVariants: name variants snps indels
+      af_0_10 af_10_30 af_30_70 af_70_90 af_90_100, bins of AF in percent
+
+--merge
+    Combines stat*.tsv of many project directories into one TSV
+    Columns are project, table, name, key and value
+    With `--stat statFinal`, one wide table of project, name and the columns
+
+"#,
+        )
+        .arg(
+            Arg::with_name("dir")
+                .help("The project directory, more than one with --merge")
+                .default_value(".")
+                .multiple(true)
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("resources")
+                .long("resources")
+                .help("Runtime and memory of each stage"),
+        )
+        .arg(
+            Arg::with_name("html")
+                .long("html")
+                .help("A self-contained HTML report"),
+        )
+        .arg(
+            Arg::with_name("table")
+                .long("table")
+                .takes_value(true)
+                .value_name("FILE.tsv")
+                .help("JSON of a TSV table"),
+        )
+        .arg(
+            Arg::with_name("merge")
+                .long("merge")
+                .help("Combine stats of many projects"),
+        )
+        .arg(
+            Arg::with_name("stat")
+                .long("stat")
+                .takes_value(true)
+                .requires("merge")
+                .help("Only this table, e.g. statFinal"),
+        )
+        .group(
+            ArgGroup::with_name("mode")
+                .args(&["resources", "html", "table", "merge"])
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("outfile")
+                .short("o")
+                .long("outfile")
+                .takes_value(true)
+                .default_value("stdout")
+                .empty_values(false)
+                .help("Output filename. [stdout] for screen"),
+        )
+}
+
+// command implementation
+pub fn execute(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
+    let dirs: Vec<&str> = args.values_of("dir").unwrap().collect();
+    if dirs.len() > 1 && !args.is_present("merge") {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Only --merge takes more than one directory",
+        ));
+    }
+    let dir = Path::new(dirs[0]);
+    let mut writer = intspan::writer(args.value_of("outfile").unwrap());
+
+    if args.is_present("resources") {
+        let lines = report_resources(dir)?;
+        for line in lines {
+            writer.write_all((line + "\n").as_ref())?;
+        }
+    } else if args.is_present("html") {
+        let html = report_html(dir)?;
+        writer.write_all(html.as_ref())?;
+    } else if args.is_present("table") {
+        write_table(Path::new(args.value_of("table").unwrap()))?;
+    } else if args.is_present("merge") {
+        let lines = report_merge(&dirs, args.value_of("stat"))?;
+        for line in lines {
+            writer.write_all((line + "\n").as_ref())?;
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Default, Debug)]
+struct Resource {
+    stage: String,
+    part: String,
+    wall: f64,
+    cpu: f64,
+    percent: String,
+    rss: u64,
+    exit: String,
+}
+
+fn report_resources(dir: &Path) -> std::result::Result<Vec<String>, std::io::Error> {
+    let res_dir = dir.join("resources");
+    if !res_dir.is_dir() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("Can't find {}", res_dir.display()),
+        ));
+    }
+
+    let mut records: Vec<Resource> = vec![];
+    for entry in WalkDir::new(&res_dir).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
+        let entry = entry?;
+        let path = entry.path();
+        if !entry.file_type().is_file() || path.extension().unwrap_or_default() != "txt" {
+            continue;
+        }
+
+        let mut record = parse_time(path);
+        record.part = match path.parent().unwrap().strip_prefix(&res_dir) {
+            Ok(p) if p.as_os_str().is_empty() => ".".to_string(),
+            Ok(p) => p.to_string_lossy().to_string(),
+            Err(_) => ".".to_string(),
+        };
+        records.push(record);
+    }
+
+    let mut lines = vec![];
+    lines.push("| Stage | Part | Wall | CPU | %CPU | Max RSS | Exit |".to_string());
+    lines.push("|:------|:-----|-----:|----:|-----:|--------:|-----:|".to_string());
+    for r in &records {
+        lines.push(format!(
+            "| {} | {} | {} | {} | {} | {} | {} |",
+            r.stage,
+            r.part,
+            format_duration(r.wall),
+            format_duration(r.cpu),
+            r.percent,
+            format_kbytes(r.rss),
+            r.exit
+        ));
+    }
+
+    // Summary of each script, keeps the order of first appearance
+    let mut order: Vec<String> = vec![];
+    let mut summary: BTreeMap<String, (usize, f64, f64, u64)> = BTreeMap::new();
+    for r in &records {
+        let script = r.stage.split_whitespace().next().unwrap_or("").to_string();
+        if !summary.contains_key(&script) {
+            order.push(script.clone());
+        }
+        let entry = summary.entry(script).or_insert((0, 0.0, 0.0, 0));
+        entry.0 += 1;
+        entry.1 += r.wall;
+        entry.2 += r.cpu;
+        entry.3 = entry.3.max(r.rss);
+    }
+
+    lines.push("".to_string());
+    lines.push("| Script | Runs | Wall | CPU | Max RSS |".to_string());
+    lines.push("|:-------|-----:|-----:|----:|--------:|".to_string());
+    for script in &order {
+        let (runs, wall, cpu, rss) = summary.get(script).unwrap();
+        lines.push(format!(
+            "| {} | {} | {} | {} | {} |",
+            script,
+            runs,
+            format_duration(*wall),
+            format_duration(*cpu),
+            format_kbytes(*rss),
+        ));
+    }
+
+    Ok(lines)
+}
+
+// Wall time of each script
+pub fn script_walls(dir: &Path) -> BTreeMap<String, f64> {
+    let mut walls = BTreeMap::new();
+    for entry in WalkDir::new(dir.join("resources"))
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if !entry.file_type().is_file() || path.extension().unwrap_or_default() != "txt" {
+            continue;
+        }
+        let record = parse_time(path);
+        let script = record
+            .stage
+            .split_whitespace()
+            .next()
+            .unwrap_or("")
+            .to_string();
+        *walls.entry(script).or_insert(0.0) += record.wall;
+    }
+
+    walls
+}
+
+// Output of `/usr/bin/time -v`
+fn parse_time(path: &Path) -> Resource {
+    let mut record = Resource::default();
+    let mut user = 0.0;
+    let mut system = 0.0;
+
+    let reader = intspan::reader(path.to_str().unwrap());
+    for line in reader.lines().map_while(|r| r.ok()) {
+        let line = line.trim();
+        let (key, value) = match line.rfind(": ") {
+            Some(i) => (&line[..i], line[i + 2..].trim()),
+            None => continue,
+        };
+
+        match key {
+            "Command being timed" => {
+                // "bash /path/to/4_anchors.sh 4_unitigs_bcalm"
+                let command = value.trim_matches('"');
+                let mut fields: Vec<&str> = command.split_whitespace().collect();
+                if fields.first() == Some(&"bash") {
+                    fields.remove(0);
+                }
+                if let Some(first) = fields.first_mut() {
+                    *first = first.rsplit('/').next().unwrap();
+                }
+                record.stage = fields.join(" ");
+            }
+            "User time (seconds)" => user = value.parse().unwrap_or(0.0),
+            "System time (seconds)" => system = value.parse().unwrap_or(0.0),
+            "Percent of CPU this job got" => record.percent = value.to_string(),
+            "Elapsed (wall clock) time (h:mm:ss or m:ss)" => record.wall = parse_elapsed(value),
+            "Maximum resident set size (kbytes)" => record.rss = value.parse().unwrap_or(0),
+            "Exit status" => record.exit = value.to_string(),
+            _ => {}
+        }
+    }
+    record.cpu = user + system;
+
+    record
+}
+
+// "1:04:22" or "0:33.19"
+fn parse_elapsed(value: &str) -> f64 {
+    value.split(':').fold(0.0, |acc, part| {
+        acc * 60.0 + part.parse::<f64>().unwrap_or(0.0)
+    })
+}
+
+// Same as `time_format` in header.tera.sh
+pub fn format_duration(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+fn format_kbytes(kbytes: u64) -> String {
+    let units = ["K", "M", "G", "T"];
+    let mut size = kbytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.2}{}", size, units[unit])
+}
+
+// A TSV table written by stats scripts
+pub struct Table {
+    pub name: String,
+    pub keys: Vec<String>,
+    pub rows: Vec<Vec<Option<String>>>,
+}
+
+// Empty cells are None
+pub fn parse_table(path: &Path) -> std::result::Result<Table, std::io::Error> {
+    let tsv = std::fs::read_to_string(path)?;
+    let name = path
+        .file_stem()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut lines = tsv.lines();
+    let keys: Vec<String> = match lines.next() {
+        Some(line) if !line.is_empty() => line.split('\t').map(|k| k.to_string()).collect(),
+        _ => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("No headers in {}", path.display()),
+            ))
+        }
+    };
+
+    let rows = lines
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            line.split('\t')
+                .map(|c| {
+                    if c.is_empty() {
+                        None
+                    } else {
+                        Some(c.to_string())
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(Table { name, keys, rows })
+}
+
+fn write_table(path: &Path) -> std::result::Result<(), std::io::Error> {
+    if path.extension().unwrap_or_default() != "tsv" {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("{} is not a TSV table", path.display()),
+        ));
+    }
+    let table = parse_table(path)?;
+
+    let rows: Vec<serde_json::Value> = table
+        .rows
+        .iter()
+        .map(|row| {
+            let mut obj = serde_json::Map::new();
+            for (key, value) in table.keys.iter().zip(row.iter()) {
+                let v = match value {
+                    None => serde_json::Value::Null,
+                    Some(s) => match serde_json::from_str::<serde_json::Number>(s) {
+                        Ok(n) => serde_json::Value::Number(n),
+                        _ => serde_json::Value::String(s.clone()),
+                    },
+                };
+                obj.insert(key.clone(), v);
+            }
+            serde_json::Value::Object(obj)
+        })
+        .collect();
+    let json = serde_json::json!({
+        "table": table.name,
+        "columns": table.keys,
+        "rows": rows,
+    });
+
+    let mut writer = intspan::writer(path.with_extension("json").to_str().unwrap());
+    writer.write_all(serde_json::to_string_pretty(&json).unwrap().as_ref())?;
+    writer.write_all(b"\n")?;
+
+    Ok(())
+}
+
+fn report_merge(
+    dirs: &[&str],
+    stat: Option<&str>,
+) -> std::result::Result<Vec<String>, std::io::Error> {
+    let mut lines = vec![];
+    let mut wide_keys: Option<Vec<String>> = None;
+
+    for d in dirs {
+        let dir = Path::new(d);
+        let project = dir
+            .canonicalize()?
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let mut tsvs: Vec<String> = std::fs::read_dir(dir)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|f| f.starts_with("stat") && f.ends_with(".tsv"))
+            .filter(|f| stat.is_none_or(|s| f.as_str() == format!("{}.tsv", s)))
+            .collect();
+        tsvs.sort();
+
+        for f in tsvs {
+            let table = match parse_table(&dir.join(&f)) {
+                Ok(t) => t,
+                Err(_) => continue,
+            };
+
+            if stat.is_some() {
+                // Columns of the first project
+                let keys = wide_keys.get_or_insert_with(|| {
+                    lines.push(format!("project\t{}", table.keys.join("\t")));
+                    table.keys.clone()
+                });
+                for row in &table.rows {
+                    let mut fields = vec![project.clone()];
+                    for key in keys.iter() {
+                        let value = table
+                            .keys
+                            .iter()
+                            .position(|k| k == key)
+                            .and_then(|i| row.get(i).cloned().flatten());
+                        fields.push(value.unwrap_or_default());
+                    }
+                    lines.push(fields.join("\t"));
+                }
+            } else {
+                for row in &table.rows {
+                    let name = row[0].clone().unwrap_or_default();
+                    for (key, value) in table.keys.iter().zip(row.iter()).skip(1) {
+                        lines.push(format!(
+                            "{}\t{}\t{}\t{}\t{}",
+                            project,
+                            table.name,
+                            name,
+                            key,
+                            value.clone().unwrap_or_default()
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    if stat.is_none() {
+        lines.insert(0, "project\ttable\tname\tkey\tvalue".to_string());
+    }
+
+    Ok(lines)
+}
+
+// Markdown files of each section, matched by prefixes of file names
+const SECTIONS: [(&str, &[&str]); 3] = [
+    (
+        "Reads",
+        &[
+            "statInsertSize",
+            "statKAT",
+            "statSgaStats",
+            "statReads",
+            "statTrimReads",
+            "statScreen",
+            "statKmerProfile",
+            "statMergeReads",
+            "statQuorum",
+            "statVariants",
+        ],
+    ),
+    (
+        "Anchors",
+        &[
+            "statAnchors",
+            "statUnitigs",
+            "statMRUnitigs",
+            "statMergeAnchors",
+            "statOtherAnchors",
+            "statMeta",
+        ],
+    ),
+    (
+        "Assemblies",
+        &["statPolish", "statFinal", "statMerqury", "statBusco"],
+    ),
+];
+
+fn report_html(dir: &Path) -> std::result::Result<String, std::io::Error> {
+    if !dir.is_dir() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("Can't find {}", dir.display()),
+        ));
+    }
+
+    let mut mds: Vec<String> = std::fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|f| f.starts_with("stat") && f.ends_with(".md"))
+        .collect();
+    mds.sort();
+
+    let project = dir
+        .canonicalize()?
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut html = String::new();
+    html += "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n";
+    writeln!(html, "<title>{}</title>", escape(&project)).unwrap();
+    html += HTML_STYLE;
+    html += "</head>\n<body>\n";
+    writeln!(html, "<h1>{}</h1>", escape(&project)).unwrap();
+
+    for (section, prefixes) in SECTIONS.iter() {
+        let mut body = String::new();
+
+        match *section {
+            "Reads" => {
+                body += &chart_khist(dir);
+                body += &chart_ihist(dir);
+            }
+            "Anchors" => {
+                body += &chart_anchor_lengths(dir);
+                body += &chart_coverage(dir);
+            }
+            _ => {}
+        }
+
+        for prefix in prefixes.iter() {
+            let matched: Vec<String> = mds
+                .iter()
+                .filter(|f| f.starts_with(prefix))
+                .cloned()
+                .collect();
+            for f in matched {
+                body += &md_to_html(&std::fs::read_to_string(dir.join(&f))?);
+                mds.retain(|m| *m != f);
+            }
+        }
+
+        if *section == "Assemblies" {
+            body += &quast_html(dir);
+        }
+
+        if !body.is_empty() {
+            writeln!(html, "<h2>{}</h2>\n{}", section, body).unwrap();
+        }
+    }
+
+    // Others
+    if !mds.is_empty() {
+        html += "<h2>Others</h2>\n";
+        for f in &mds {
+            html += &md_to_html(&std::fs::read_to_string(dir.join(f))?);
+        }
+    }
+
+    writeln!(
+        html,
+        "<footer>Created by anchr {}</footer>\n</body>\n</html>",
+        env!("CARGO_PKG_VERSION")
+    )
+    .unwrap();
+
+    Ok(html)
+}
+
+const HTML_STYLE: &str = r#"<style>
+body { font-family: sans-serif; margin: 2em; color: #222; }
+h2 { border-bottom: 1px solid #ccc; }
+table { border-collapse: collapse; margin: 1em 0; font-size: 0.9em; }
+th, td { border: 1px solid #ccc; padding: 2px 8px; }
+th { background: #eee; }
+pre { background: #f6f6f6; padding: 0.5em; }
+svg { display: block; margin: 1em 0; }
+footer { margin-top: 2em; color: #888; font-size: 0.8em; }
+</style>
+"#;
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Tables, captions and code blocks of stat*.md
+fn md_to_html(md: &str) -> String {
+    let mut html = String::new();
+    let mut rows: Vec<Vec<String>> = vec![];
+    let mut aligns: Vec<&str> = vec![];
+    let mut in_pre = false;
+
+    let flush = |html: &mut String, rows: &mut Vec<Vec<String>>, aligns: &[&str]| {
+        if rows.is_empty() {
+            return;
+        }
+        html.push_str("<table>\n");
+        for (i, row) in rows.iter().enumerate() {
+            let tag = if i == 0 { "th" } else { "td" };
+            html.push_str("<tr>");
+            for (j, cell) in row.iter().enumerate() {
+                let align = aligns.get(j).copied().unwrap_or("left");
+                write!(
+                    html,
+                    "<{} style=\"text-align:{}\">{}</{}>",
+                    tag,
+                    align,
+                    escape(cell),
+                    tag
+                )
+                .unwrap();
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+        rows.clear();
+    };
+
+    for line in md.lines() {
+        if line.starts_with("```") {
+            html.push_str(if in_pre { "</pre>\n" } else { "<pre>" });
+            in_pre = !in_pre;
+            continue;
+        }
+        if in_pre {
+            html.push_str(&escape(line));
+            html.push('\n');
+            continue;
+        }
+
+        let line = line.trim();
+        if line.starts_with('|') {
+            let cells: Vec<String> = line
+                .trim_matches('|')
+                .split('|')
+                .map(|c| c.trim().to_string())
+                .collect();
+            if cells
+                .iter()
+                .all(|c| !c.is_empty() && c.chars().all(|ch| ch == ':' || ch == '-'))
+            {
+                aligns = cells
+                    .iter()
+                    .map(|c| if c.ends_with(':') { "right" } else { "left" })
+                    .collect();
+            } else {
+                rows.push(cells);
+            }
+            continue;
+        }
+
+        flush(&mut html, &mut rows, &aligns);
+        if let Some(caption) = line.strip_prefix("Table:") {
+            writeln!(html, "<h3>{}</h3>", escape(caption.trim())).unwrap();
+        } else if !line.is_empty() {
+            writeln!(html, "<p>{}</p>", escape(line)).unwrap();
+        }
+    }
+    flush(&mut html, &mut rows, &aligns);
+
+    html
+}
+
+// 9_quast/report.tsv
+fn quast_html(dir: &Path) -> String {
+    let path = dir.join("9_quast").join("report.tsv");
+    if !path.is_file() {
+        return String::new();
+    }
+
+    let mut md = "Table: QUAST\n\n".to_string();
+    let reader = intspan::reader(path.to_str().unwrap());
+    for (i, line) in reader.lines().map_while(|r| r.ok()).enumerate() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        writeln!(md, "| {} |", fields.join(" | ")).unwrap();
+        if i == 0 {
+            let aligns: Vec<&str> = (0..fields.len())
+                .map(|j| if j == 0 { ":--" } else { "--:" })
+                .collect();
+            writeln!(md, "|{}|", aligns.join("|")).unwrap();
+        }
+    }
+
+    md_to_html(&md)
+}
+
+fn first_file(dir: &Path, candidates: &[&str]) -> Option<std::path::PathBuf> {
+    candidates.iter().map(|c| dir.join(c)).find(|p| p.is_file())
+}
+
+// Columns of depth and counts, lines starting with # are skipped
+fn read_xy(path: &Path) -> Vec<(f64, f64)> {
+    let reader = intspan::reader(path.to_str().unwrap());
+    reader
+        .lines()
+        .map_while(|r| r.ok())
+        .filter(|l| !l.starts_with('#'))
+        .filter_map(|l| {
+            let fields: Vec<&str> = l.split_whitespace().collect();
+            if fields.len() < 2 {
+                return None;
+            }
+            let x = fields[0].parse().ok()?;
+            let y = fields[fields.len() - 1].parse().ok()?;
+            Some((x, y))
+        })
+        .collect()
+}
+
+fn chart_khist(dir: &Path) -> String {
+    let path = match first_file(
+        dir,
+        &[
+            "2_illumina/trim/R.khist.txt",
+            "2_illumina/trim/S.khist.txt",
+            "2_illumina/trim/T.khist.txt",
+        ],
+    ) {
+        Some(p) => p,
+        None => return String::new(),
+    };
+
+    let hist = super::kmer_profile::read_hist(path.to_str().unwrap());
+    if hist.len() < 3 {
+        return String::new();
+    }
+
+    // Zoom in on the main peak beyond the trough of erroneous k-mers
+    let trough = (1..hist.len() - 1)
+        .find(|&d| hist[d + 1] > hist[d])
+        .unwrap_or(1);
+    let peak = (trough..hist.len())
+        .max_by(|&a, &b| hist[a].total_cmp(&hist[b]))
+        .unwrap_or(trough);
+    let max = (peak * 4).max(trough + 10).min(hist.len() - 1);
+    let ymax = hist[trough..=max].iter().cloned().fold(0.0, f64::max) * 1.2;
+
+    let points: Vec<(f64, f64)> = (1..=max).map(|d| (d as f64, hist[d].min(ymax))).collect();
+
+    svg_chart(
+        "K-mer histogram",
+        "Depth",
+        "K-mers",
+        &points,
+        false,
+        &[(trough as f64, "trough"), (peak as f64, "peak")],
+    )
+}
+
+fn chart_ihist(dir: &Path) -> String {
+    let path = match first_file(
+        dir,
+        &[
+            "2_illumina/insert_size/R.ihist.genome.txt",
+            "2_illumina/insert_size/R.ihist.tadpole.txt",
+        ],
+    ) {
+        Some(p) => p,
+        None => return String::new(),
+    };
+
+    let mut points = read_xy(&path);
+    let total: f64 = points.iter().map(|p| p.1).sum();
+    if total <= 0.0 {
+        return String::new();
+    }
+
+    // Drops the long tail
+    let mut acc = 0.0;
+    let idx = points
+        .iter()
+        .position(|p| {
+            acc += p.1;
+            acc >= total * 0.99
+        })
+        .unwrap_or(points.len() - 1);
+    points.truncate(idx + 1);
+
+    svg_chart("Insert sizes", "Insert size", "Pairs", &points, false, &[])
+}
+
+fn chart_anchor_lengths(dir: &Path) -> String {
+    let path = match first_file(dir, &["7_merge_anchors/anchor.merge.fasta"]) {
+        Some(p) => p,
+        None => return String::new(),
+    };
+
+    let lengths = super::select::read_lengths(&path);
+    let max = match lengths.iter().max() {
+        Some(m) if *m > 0 => *m as f64,
+        _ => return String::new(),
+    };
+
+    let bins = 40;
+    let width = (max / bins as f64).ceil();
+    let mut counts = vec![0.0; bins + 1];
+    for l in &lengths {
+        counts[(*l as f64 / width) as usize] += 1.0;
+    }
+    let points: Vec<(f64, f64)> = counts
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (i as f64 * width, *c))
+        .collect();
+
+    svg_chart(
+        "Lengths of anchors",
+        "Length",
+        "Anchors",
+        &points,
+        true,
+        &[],
+    )
+}
+
+fn chart_coverage(dir: &Path) -> String {
+    // the merged anchors, or the first one of others
+    let mut path = dir.join("7_merge_anchors/anchor/basecov.hist.tsv");
+    if !path.is_file() {
+        let found = WalkDir::new(dir)
+            .max_depth(4)
+            .sort_by(|a, b| a.file_name().cmp(b.file_name()))
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .find(|e| e.file_name() == "basecov.hist.tsv");
+        path = match found {
+            Some(e) => e.path().to_path_buf(),
+            None => return String::new(),
+        };
+    }
+
+    let env = super::select::read_env(&path.with_file_name("env.json"));
+    let median = super::select::env_value(&env, "median");
+    let lower = super::select::env_value(&env, "lower");
+    let upper = super::select::env_value(&env, "upper");
+
+    let mut points = read_xy(&path);
+    points.retain(|p| p.0 > 0.0);
+    if points.is_empty() {
+        return String::new();
+    }
+    let xmax = if upper > 0.0 {
+        upper * 1.5
+    } else if median > 0.0 {
+        median * 4.0
+    } else {
+        f64::MAX
+    };
+    points.retain(|p| p.0 <= xmax);
+
+    let mut marks = vec![];
+    if lower > 0.0 {
+        marks.push((lower, "lower"));
+    }
+    if median > 0.0 {
+        marks.push((median, "median"));
+    }
+    if upper > 0.0 {
+        marks.push((upper, "upper"));
+    }
+
+    let title = format!(
+        "Base coverages of {}",
+        path.parent()
+            .and_then(|p| p.strip_prefix(dir).ok())
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default()
+    );
+    svg_chart(&title, "Coverage", "Bases", &points, false, &marks)
+}
+
+// A line or bar chart with vertical marks
+fn svg_chart(
+    title: &str,
+    xlabel: &str,
+    ylabel: &str,
+    points: &[(f64, f64)],
+    bars: bool,
+    marks: &[(f64, &str)],
+) -> String {
+    if points.is_empty() {
+        return String::new();
+    }
+
+    let (width, height) = (640.0, 320.0);
+    let (left, right, top, bottom) = (70.0, 20.0, 30.0, 50.0);
+    let (plot_w, plot_h) = (width - left - right, height - top - bottom);
+
+    let xmin = points.iter().map(|p| p.0).fold(f64::MAX, f64::min).min(0.0);
+    let mut xmax = points.iter().map(|p| p.0).fold(f64::MIN, f64::max);
+    if bars && points.len() > 1 {
+        xmax += points[1].0 - points[0].0;
+    }
+    // marks are always visible
+    xmax = marks.iter().map(|m| m.0).fold(xmax, f64::max);
+    let xmax = if xmax > xmin { xmax } else { xmin + 1.0 };
+    let ymax = points.iter().map(|p| p.1).fold(0.0, f64::max);
+    let ymax = if ymax > 0.0 { ymax * 1.05 } else { 1.0 };
+
+    let sx = |x: f64| left + (x - xmin) / (xmax - xmin) * plot_w;
+    let sy = |y: f64| top + plot_h - y / ymax * plot_h;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-size="11">"#,
+        w = width,
+        h = height
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<text x="{}" y="18" font-size="14" text-anchor="middle">{}</text>"#,
+        width / 2.0,
+        escape(title)
+    )
+    .unwrap();
+
+    // axes and ticks
+    writeln!(
+        svg,
+        r##"<path d="M{l},{t} V{b} H{r}" fill="none" stroke="#333"/>"##,
+        l = left,
+        t = top,
+        b = top + plot_h,
+        r = left + plot_w
+    )
+    .unwrap();
+    for i in 0..=5 {
+        let x = xmin + (xmax - xmin) * i as f64 / 5.0;
+        let y = ymax * i as f64 / 5.0;
+        writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+            sx(x),
+            top + plot_h + 15.0,
+            format_number(x)
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="end">{}</text>"#,
+            left - 5.0,
+            sy(y) + 4.0,
+            format_number(y)
+        )
+        .unwrap();
+    }
+    writeln!(
+        svg,
+        r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+        left + plot_w / 2.0,
+        height - 10.0,
+        escape(xlabel)
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<text x="15" y="{:.1}" text-anchor="middle" transform="rotate(-90 15 {:.1})">{}</text>"#,
+        top + plot_h / 2.0,
+        top + plot_h / 2.0,
+        escape(ylabel)
+    )
+    .unwrap();
+
+    // data
+    if bars {
+        let bar_w = if points.len() > 1 {
+            sx(points[1].0) - sx(points[0].0)
+        } else {
+            plot_w
+        };
+        for (x, y) in points {
+            writeln!(
+                svg,
+                r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="steelblue" stroke="white"/>"#,
+                sx(*x),
+                sy(*y),
+                bar_w,
+                top + plot_h - sy(*y)
+            )
+            .unwrap();
+        }
+    } else {
+        let coords: Vec<String> = points
+            .iter()
+            .map(|(x, y)| format!("{:.1},{:.1}", sx(*x), sy(*y)))
+            .collect();
+        writeln!(
+            svg,
+            r#"<polyline points="{}" fill="none" stroke="steelblue" stroke-width="1.5"/>"#,
+            coords.join(" ")
+        )
+        .unwrap();
+    }
+
+    for (i, (x, label)) in marks.iter().enumerate() {
+        writeln!(
+            svg,
+            r#"<line x1="{x:.1}" y1="{t}" x2="{x:.1}" y2="{b}" stroke="firebrick" stroke-dasharray="4,3"/>"#,
+            x = sx(*x),
+            t = top,
+            b = top + plot_h
+        )
+        .unwrap();
+        // labels near the right border are placed on the left side
+        let (tx, anchor) = if sx(*x) > left + plot_w * 0.8 {
+            (sx(*x) - 3.0, "end")
+        } else {
+            (sx(*x) + 3.0, "start")
+        };
+        writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="{}" fill="firebrick">{} {}</text>"#,
+            tx,
+            top + 10.0 + 12.0 * i as f64,
+            anchor,
+            label,
+            format_number(*x)
+        )
+        .unwrap();
+    }
+
+    svg += "</svg>\n";
+    svg
+}
+
+fn format_number(v: f64) -> String {
+    let abs = v.abs();
+    if abs >= 1e9 {
+        format!("{:.1}G", v / 1e9)
+    } else if abs >= 1e6 {
+        format!("{:.1}M", v / 1e6)
+    } else if abs >= 1e4 {
+        format!("{:.1}k", v / 1e3)
+    } else if abs >= 10.0 || v.fract() == 0.0 {
+        format!("{:.0}", v)
+    } else {
+        format!("{:.1}", v)
+    }
+}
@@ -0,0 +1,334 @@
+use clap::*;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::path::Path;
+
+// Create clap subcommand arguments
+pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("select")
+        .about("Selects the best Q/L/X combination of anchors")
+        .after_help(
+            r#"
+Reads anchors of down-sampled parts in 4_unitigs_* and 6_unitigs_*,
+averages statistics of parts with the same unitigger and Q/L/X,
+and ranks combinations by a weighted score
+
+Metrics of --weights
+    * n50, N50 of anchors
+    * sum, total length of anchors
+    * count, number of anchors
+    * mapped, MAPPED_RATIO of reads to unitigs
+    * cv, MAD / median of the base coverage
+
+Each metric is divided by its maximum among combinations before weighting,
+so negative weights penalize larger values
+
+"#,
+        )
+        .arg(
+            Arg::with_name("dir")
+                .help("The project directory")
+                .default_value(".")
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("weights")
+                .long("weights")
+                .help("Weights of metrics")
+                .takes_value(true)
+                .default_value("n50=1,sum=1,count=-0.5,mapped=0.5,cv=-0.5")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("outfile")
+                .short("o")
+                .long("outfile")
+                .takes_value(true)
+                .default_value("stdout")
+                .empty_values(false)
+                .help("Output filename. [stdout] for screen"),
+        )
+}
+
+const METRICS: [&str; 5] = ["n50", "sum", "count", "mapped", "cv"];
+
+// command implementation
+pub fn execute(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
+    let dir = Path::new(args.value_of("dir").unwrap());
+    let weights = parse_weights(args.value_of("weights").unwrap())?;
+
+    let mut combos = read_combos(dir)?;
+    if combos.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("Can't find anchors in {}", dir.display()),
+        ));
+    }
+
+    // score
+    let max: Vec<f64> = (0..METRICS.len())
+        .map(|i| combos.iter().map(|c| c.metrics()[i]).fold(0.0, f64::max))
+        .collect();
+    for c in combos.iter_mut() {
+        c.score = c
+            .metrics()
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                if max[i] > 0.0 {
+                    weights[i] * v / max[i]
+                } else {
+                    0.0
+                }
+            })
+            .sum();
+    }
+    combos.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    let mut writer = intspan::writer(args.value_of("outfile").unwrap());
+
+    writer.write_all(format!("Recommended: {}\n\n", combos[0].options()).as_ref())?;
+
+    writer.write_all("Table: statSelect\n\n".as_ref())?;
+    writer.write_all(
+        "| Rank | Name | Parts | N50 | Sum | # | Mapped% | median | MAD | Score |\n".as_ref(),
+    )?;
+    writer.write_all("|--:|:--|--:|--:|--:|--:|--:|--:|--:|--:|\n".as_ref())?;
+    for (i, c) in combos.iter().enumerate() {
+        writer.write_all(
+            format!(
+                "| {} | {} | {} | {:.0} | {:.0} | {:.0} | {:.2}% | {:.1} | {:.1} | {:.3} |\n",
+                i + 1,
+                c.name(),
+                c.parts,
+                c.n50,
+                c.sum,
+                c.count,
+                c.mapped * 100.0,
+                c.median,
+                c.mad,
+                c.score
+            )
+            .as_ref(),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Default, Debug, Clone)]
+struct Combo {
+    unitigger: String,
+    merged: bool,
+    qual: String,
+    len: String,
+    cov: String,
+    parts: usize,
+    n50: f64,
+    sum: f64,
+    count: f64,
+    mapped: f64,
+    median: f64,
+    mad: f64,
+    score: f64,
+}
+
+impl Combo {
+    fn name(&self) -> String {
+        if self.merged {
+            format!("{} MRX{}", self.unitigger, self.cov)
+        } else {
+            format!(
+                "{} Q{}L{}X{}",
+                self.unitigger, self.qual, self.len, self.cov
+            )
+        }
+    }
+
+    fn metrics(&self) -> [f64; 5] {
+        let cv = if self.median > 0.0 {
+            self.mad / self.median
+        } else {
+            0.0
+        };
+        [self.n50, self.sum, self.count, self.mapped, cv]
+    }
+
+    // Options of `anchr template`
+    fn options(&self) -> String {
+        if self.merged {
+            format!(
+                "--merge --cov \"{}\" --unitigger \"{}\"",
+                self.cov, self.unitigger
+            )
+        } else {
+            format!(
+                "--qual \"{}\" --len \"{}\" --cov \"{}\" --unitigger \"{}\"",
+                self.qual, self.len, self.cov, self.unitigger
+            )
+        }
+    }
+}
+
+fn parse_weights(s: &str) -> std::result::Result<Vec<f64>, std::io::Error> {
+    let mut weights = vec![0.0; METRICS.len()];
+
+    for pair in s.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        let (key, value) = match pair.find('=') {
+            Some(i) => (&pair[..i], &pair[i + 1..]),
+            None => ("", ""),
+        };
+        let idx = METRICS.iter().position(|m| *m == key);
+        // nan or inf would make scores incomparable
+        let value = value.parse::<f64>().ok().filter(|v| v.is_finite());
+        match (idx, value) {
+            (Some(i), Some(v)) => weights[i] = v,
+            _ => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Invalid weight {}", pair),
+                ))
+            }
+        }
+    }
+
+    Ok(weights)
+}
+
+// 4_unitigs_bcalm/Q25L60X40P000 or 6_unitigs_bcalm/MRX40P000
+fn read_combos(dir: &Path) -> std::result::Result<Vec<Combo>, std::io::Error> {
+    let mut combo_of: BTreeMap<String, Combo> = BTreeMap::new();
+
+    let mut stage_dirs: Vec<_> = std::fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    stage_dirs.sort();
+
+    for stage_dir in stage_dirs {
+        let stage = stage_dir.file_name().unwrap().to_string_lossy().to_string();
+        let unitigger = if let Some(u) = stage.strip_prefix("4_unitigs_") {
+            u.to_string()
+        } else if let Some(u) = stage.strip_prefix("6_unitigs_") {
+            u.to_string()
+        } else {
+            continue;
+        };
+
+        let mut part_dirs: Vec<_> = std::fs::read_dir(&stage_dir)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.join("anchor").join("anchor.fasta").is_file())
+            .collect();
+        part_dirs.sort();
+
+        for part_dir in part_dirs {
+            let part = part_dir.file_name().unwrap().to_string_lossy().to_string();
+            let mut combo = match parse_part(&part) {
+                Some(c) => c,
+                None => continue,
+            };
+            combo.unitigger = unitigger.clone();
+
+            let lengths = read_lengths(&part_dir.join("anchor").join("anchor.fasta"));
+            let env = read_env(&part_dir.join("anchor").join("env.json"));
+
+            let entry = combo_of.entry(combo.name()).or_insert(combo);
+            entry.parts += 1;
+            entry.n50 += n50(&lengths) as f64;
+            entry.sum += lengths.iter().sum::<usize>() as f64;
+            entry.count += lengths.len() as f64;
+            entry.mapped += env_value(&env, "MAPPED_RATIO");
+            entry.median += env_value(&env, "median");
+            entry.mad += env_value(&env, "MAD");
+        }
+    }
+
+    // averages of parts
+    let combos = combo_of
+        .into_values()
+        .map(|mut c| {
+            let n = c.parts as f64;
+            c.n50 /= n;
+            c.sum /= n;
+            c.count /= n;
+            c.mapped /= n;
+            c.median /= n;
+            c.mad /= n;
+            c
+        })
+        .collect();
+
+    Ok(combos)
+}
+
+fn parse_part(part: &str) -> Option<Combo> {
+    let mut combo = Combo::default();
+
+    let rest = if let Some(rest) = part.strip_prefix("MR") {
+        combo.merged = true;
+        rest
+    } else {
+        let rest = part.strip_prefix('Q')?;
+        let l = rest.find('L')?;
+        combo.qual = rest[..l].to_string();
+        let rest = &rest[l + 1..];
+        let x = rest.find('X')?;
+        combo.len = rest[..x].to_string();
+        &rest[x..]
+    };
+
+    let rest = rest.strip_prefix('X')?;
+    let p = rest.find('P')?;
+    combo.cov = rest[..p].to_string();
+
+    Some(combo)
+}
+
+pub fn read_lengths(path: &Path) -> Vec<usize> {
+    let mut lengths = vec![];
+
+    let reader = intspan::reader(path.to_str().unwrap());
+    for line in reader.lines().map_while(|r| r.ok()) {
+        if line.starts_with('>') {
+            lengths.push(0);
+        } else if let Some(last) = lengths.last_mut() {
+            *last += line.trim_end().len();
+        }
+    }
+
+    lengths
+}
+
+fn n50(lengths: &[usize]) -> usize {
+    let mut sorted = lengths.to_vec();
+    sorted.sort_unstable_by(|a, b| b.cmp(a));
+
+    let total: usize = sorted.iter().sum();
+    let mut acc = 0;
+    for l in sorted {
+        acc += l;
+        if acc * 2 >= total {
+            return l;
+        }
+    }
+
+    0
+}
+
+pub fn read_env(path: &Path) -> serde_json::Value {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or(serde_json::Value::Null)
+}
+
+// Values in env.json may be numbers or strings
+pub fn env_value(env: &serde_json::Value, key: &str) -> f64 {
+    match &env[key] {
+        serde_json::Value::Number(n) => n.as_f64().unwrap_or(0.0),
+        serde_json::Value::String(s) => s.parse().unwrap_or(0.0),
+        _ => 0.0,
+    }
+}
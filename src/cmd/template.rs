@@ -1,7 +1,8 @@
+use anchr::libs::template::{lint_script, InputStatus};
+use anchr::TemplateOptions;
 use clap::*;
-use itertools::Itertools;
-use std::collections::HashMap;
-use tera::{Context, Tera};
+use std::collections::BTreeMap;
+use std::path::Path;
 
 // Create clap subcommand arguments
 pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
@@ -11,11 +12,23 @@ pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
             r#"
 * Info
 
-    * --genome
+    * --genome 1000000, or auto to estimate it with `anchr kmer-profile`
     * --se
+    * --meta, metagenome mode
+        * No down sampling, unitigs, anchors, extending or polishing
+        * metaSPAdes and megahit with `--presets meta-large`
+        * 9_stat_meta.sh bins contigs by coverage and GC
     * --xmx
     * --parallel 8
     * --queue mpi
+    * --container docker
+    * --images images.tsv
+    * --templates DIR, extra stages from DIR/NAME.tera.sh
+    * --check, render for combinations of --se, --merge, --quorum, --unitigger,
+      --extend, --busco and --queue, and lint scripts with `bash -n`
+    * --plan, list scripts in run order with inputs, outputs and tools
+    * --strict
+    * --retries 0, only in 0_master.sh. Jobs of 0_bsub.sh are not rerun
 
 * Quality check
 
@@ -31,6 +44,9 @@ pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
     * --qual "25 30"
     * --len "60"
     * --filter "adapter"
+    * --screen kraken2_db, classify reads with a local database
+    * --screener kraken2
+    * --taxon "Escherichia coli", remove reads assigned outside this taxon
 
 * Post-trimming
 
@@ -41,7 +57,14 @@ pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
 
 * Mapping
     * --bwa
-    * --gatk
+    * --caller gatk|freebayes|both
+
+* Long reads
+
+    * --ont / --pacbio, glue and fill anchors with long reads instead of other contigs
+    * --longmin 1000
+    * --longidt 0.85
+    * --canu, also assemble long reads with canu besides flye
 
 * Down sampling, unitigs, and anchors
 
@@ -53,6 +76,7 @@ pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
     * --uscale 2
     * --lscale 3
     * --redo
+    * --organelle, high-copy contigs of assemblies as organelle candidates
 
 * Extend anchors
 
@@ -60,18 +84,37 @@ pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
     * --gluemin 30
     * --fillmax 100
 
+* Polish
+
+    * --polish
+    * --polisher pilon
+    * --rounds 2
+
+* Quality of assemblies
+
+    * --merqury
+    * --busco
+
+* User templates
+
+    * The first line of NAME.tera.sh may be a metadata comment
+      {# anchr: after=9_stat_final when=merge,!meta per_unitigger #}
+    * NAME of a built-in stage replaces its template
+    * Other stages run right after the `after` stage, or at the end without it
+
 "#,
         )
         // Info
         .arg(
             Arg::with_name("genome")
                 .long("genome")
-                .help("Your best guess of the haploid genome size")
+                .help("Your best guess of the haploid genome size, or auto")
                 .takes_value(true)
                 .default_value("1000000")
                 .empty_values(false),
         )
         .arg(Arg::with_name("se").long("se").help("Single end mode"))
+        .arg(Arg::with_name("meta").long("meta").help("Metagenome mode"))
         .arg(
             Arg::with_name("xmx")
                 .long("xmx")
@@ -95,6 +138,54 @@ pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
                 .takes_value(true)
                 .empty_values(false),
         )
+        .arg(
+            Arg::with_name("container")
+                .long("container")
+                .help("Run tools inside images: docker, singularity, or apptainer")
+                .takes_value(true)
+                .possible_values(&["docker", "singularity", "apptainer"])
+                .requires("images")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("images")
+                .long("images")
+                .help("A TSV file of tool and image. Unlisted tools run natively")
+                .takes_value(true)
+                .requires("container")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("templates")
+                .long("templates")
+                .help("A directory of user templates, NAME.tera.sh")
+                .takes_value(true)
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("check")
+                .long("check")
+                .help("Validate rendered scripts instead of creating them"),
+        )
+        .arg(
+            Arg::with_name("plan")
+                .long("plan")
+                .help("List files and tools of each stage instead of creating scripts")
+                .conflicts_with("check"),
+        )
+        .arg(
+            Arg::with_name("strict")
+                .long("strict")
+                .help("Stop at the first error and leave a .failed marker"),
+        )
+        .arg(
+            Arg::with_name("retries")
+                .long("retries")
+                .help("Rerun a failed stage N times in 0_master.sh, not in 0_bsub.sh")
+                .takes_value(true)
+                .default_value("0")
+                .empty_values(false),
+        )
         // Quality check
         .arg(Arg::with_name("fastqc").long("fastqc").help("Run FastQC"))
         .arg(Arg::with_name("kat").long("kat").help("Run KAT"))
@@ -152,6 +243,29 @@ pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
                 .default_value("adapter")
                 .empty_values(false),
         )
+        .arg(
+            Arg::with_name("screen")
+                .long("screen")
+                .help("The local database for contamination screening")
+                .takes_value(true)
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("screener")
+                .long("screener")
+                .help("kraken2 or mash")
+                .takes_value(true)
+                .possible_values(&["kraken2", "mash"])
+                .default_value("kraken2")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("taxon")
+                .long("taxon")
+                .help("Remove reads assigned outside this taxon")
+                .takes_value(true)
+                .empty_values(false),
+        )
         // Post-trimming
         .arg(Arg::with_name("quorum").long("quorum").help("Run quorum"))
         .arg(
@@ -185,7 +299,52 @@ pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
         .arg(
             Arg::with_name("gatk")
                 .long("gatk")
-                .help("Calling variants with GATK Mutect2"),
+                .help("Calling variants with GATK Mutect2, the same as --caller gatk"),
+        )
+        .arg(
+            Arg::with_name("caller")
+                .long("caller")
+                .help("Variant callers, GATK Mutect2 and/or freebayes")
+                .takes_value(true)
+                .possible_values(&["gatk", "freebayes", "both"])
+                .empty_values(false),
+        )
+        // Long reads
+        .arg(
+            Arg::with_name("ont")
+                .long("ont")
+                .help("Nanopore reads, to glue and fill anchors")
+                .takes_value(true)
+                .conflicts_with("pacbio")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("pacbio")
+                .long("pacbio")
+                .help("PacBio reads, to glue and fill anchors")
+                .takes_value(true)
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("longmin")
+                .long("longmin")
+                .help("Min length of long reads")
+                .takes_value(true)
+                .default_value("1000")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("longidt")
+                .long("longidt")
+                .help("Min identity of overlaps between anchors and long reads")
+                .takes_value(true)
+                .default_value("0.85")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("canu")
+                .long("canu")
+                .help("Assemble long reads with canu"),
         )
         // Down sampling, unitigs, and anchors
         .arg(
@@ -250,6 +409,11 @@ pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
                 .long("redo")
                 .help("Redo anchors when merging anchors"),
         )
+        .arg(
+            Arg::with_name("organelle")
+                .long("organelle")
+                .help("Separate organelles and plasmids in anchors of assemblies"),
+        )
         // Extend anchors
         .arg(
             Arg::with_name("extend")
@@ -272,793 +436,265 @@ pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
                 .default_value("100")
                 .empty_values(false),
         )
+        // Polish
+        .arg(
+            Arg::with_name("polish")
+                .long("polish")
+                .help("Polish the merged and extended anchors"),
+        )
+        .arg(
+            Arg::with_name("polisher")
+                .long("polisher")
+                .help("The polisher")
+                .takes_value(true)
+                .possible_values(&["pilon", "racon"])
+                .default_value("pilon")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("rounds")
+                .long("rounds")
+                .help("Rounds of polishing")
+                .takes_value(true)
+                .default_value("2")
+                .empty_values(false),
+        )
         // Extend anchors
         .arg(Arg::with_name("busco").long("busco").help("Run busco"))
+        .arg(
+            Arg::with_name("merqury")
+                .long("merqury")
+                .help("QV, k-mer completeness and mapped reads of assemblies"),
+        )
 }
 
 // command implementation
 pub fn execute(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
-    //----------------------------
-    // context from args
-    //----------------------------
-    let mut opt = HashMap::new();
+    let opt = options(args)?;
 
-    opt.insert(
-        "genome",
-        if args.is_present("genome") {
-            args.value_of("genome").unwrap()
-        } else {
-            "0"
-        },
-    );
-    opt.insert("se", if args.is_present("se") { "1" } else { "0" });
-    opt.insert(
-        "xmx",
-        if args.is_present("xmx") {
-            args.value_of("xmx").unwrap()
-        } else {
-            "0"
-        },
-    );
-    opt.insert("parallel", args.value_of("parallel").unwrap());
-    opt.insert(
-        "queue",
-        if args.is_present("queue") {
-            args.value_of("queue").unwrap()
-        } else {
-            "0"
-        },
-    );
-
-    opt.insert("reads", args.value_of("reads").unwrap());
-
-    opt.insert("trim", args.value_of("trim").unwrap());
-    opt.insert(
-        "sample",
-        if args.is_present("sample") {
-            args.value_of("sample").unwrap()
-        } else {
-            "0"
-        },
-    );
-    opt.insert("qual", args.value_of("qual").unwrap());
-    opt.insert("len", args.value_of("len").unwrap());
-    opt.insert("filter", args.value_of("filter").unwrap());
-
-    opt.insert("merge", if args.is_present("merge") { "1" } else { "0" });
-    opt.insert(
-        "prefilter",
-        if args.is_present("prefilter") {
-            args.value_of("prefilter").unwrap()
-        } else {
-            "0"
-        },
-    );
-    opt.insert("ecphase", args.value_of("ecphase").unwrap());
-
-    opt.insert(
-        "bwa",
-        if args.is_present("bwa") {
-            args.value_of("bwa").unwrap()
-        } else {
-            "0"
-        },
-    );
-    opt.insert("gatk", if args.is_present("gatk") { "1" } else { "0" });
-
-    opt.insert("cov", args.value_of("cov").unwrap());
-    opt.insert("unitigger", args.value_of("unitigger").unwrap());
-    opt.insert("splitp", args.value_of("splitp").unwrap());
-    opt.insert("statp", args.value_of("statp").unwrap());
-    opt.insert("readl", args.value_of("readl").unwrap());
-    opt.insert("uscale", args.value_of("uscale").unwrap());
-    opt.insert("lscale", args.value_of("lscale").unwrap());
-    opt.insert("redo", if args.is_present("redo") { "1" } else { "0" });
-
-    opt.insert("extend", if args.is_present("extend") { "1" } else { "0" });
-    opt.insert("gluemin", args.value_of("gluemin").unwrap());
-    opt.insert("fillmax", args.value_of("fillmax").unwrap());
-
-    let mut context = Context::new();
-    context.insert("opt", &opt);
+    if args.is_present("check") {
+        return check(&opt);
+    }
+    if args.is_present("plan") {
+        return plan(&opt);
+    }
 
     //----------------------------
-    // create scripts
+    // provenance and scripts
     //----------------------------
-    if args.is_present("fastqc") {
-        gen_fastqc(&context)?;
-    }
-    if args.is_present("insertsize") {
-        gen_insert_size(&context)?;
+    let command = std::env::args().collect::<Vec<String>>().join(" ");
+    let names = opt
+        .write_project(Path::new("."), Some(&command))
+        .unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1)
+        });
+    for name in names {
+        eprintln!("Create {}", name);
     }
-    if args.is_present("kat") {
-        gen_kat(&context)?;
-    }
-
-    gen_trim(&context)?;
 
-    gen_stat_reads(&context)?;
+    Ok(())
+}
 
-    if args.is_present("bwa") {
-        gen_bwa(&context)?;
+// Options from args, also used by `anchr batch`
+pub fn options(args: &ArgMatches) -> std::result::Result<TemplateOptions, std::io::Error> {
+    let invalid = |e: clap::Error| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.message);
+    let string = |name: &str| args.value_of(name).unwrap().to_string();
+    let optional = |name: &str| args.value_of(name).map(|s| s.to_string());
+    let number = |name: &str| value_t!(args, name, usize).map_err(invalid);
+    let float = |name: &str| value_t!(args, name, f64).map_err(invalid);
+
+    Ok(TemplateOptions {
+        genome: string("genome"),
+        se: args.is_present("se"),
+        meta: args.is_present("meta"),
+        xmx: optional("xmx"),
+        parallel: number("parallel")?,
+        queue: optional("queue"),
+        container: optional("container"),
+        images: optional("images"),
+        templates: optional("templates"),
+        strict: args.is_present("strict"),
+        retries: number("retries")?,
+
+        fastqc: args.is_present("fastqc"),
+        kat: args.is_present("kat"),
+        insertsize: args.is_present("insertsize"),
+        reads: number("reads")?,
+
+        trim: string("trim"),
+        sample: optional("sample"),
+        qual: string("qual"),
+        len: string("len"),
+        filter: string("filter"),
+        screen: optional("screen"),
+        screener: string("screener"),
+        taxon: optional("taxon"),
+
+        quorum: args.is_present("quorum"),
+        merge: args.is_present("merge"),
+        prefilter: optional("prefilter"),
+        ecphase: string("ecphase"),
+
+        bwa: optional("bwa"),
+        // --gatk is kept as --caller gatk
+        caller: optional("caller").or_else(|| {
+            if args.is_present("gatk") {
+                Some("gatk".to_string())
+            } else {
+                None
+            }
+        }),
+
+        ont: optional("ont"),
+        pacbio: optional("pacbio"),
+        longmin: number("longmin")?,
+        longidt: float("longidt")?,
+        canu: args.is_present("canu"),
+
+        cov: string("cov"),
+        unitigger: string("unitigger"),
+        splitp: number("splitp")?,
+        statp: number("statp")?,
+        readl: number("readl")?,
+        uscale: float("uscale")?,
+        lscale: float("lscale")?,
+        redo: args.is_present("redo"),
+        organelle: args.is_present("organelle"),
+
+        extend: args.is_present("extend"),
+        gluemin: number("gluemin")?,
+        fillmax: number("fillmax")?,
+
+        polish: args.is_present("polish"),
+        polisher: string("polisher"),
+        rounds: number("rounds")?,
+
+        busco: args.is_present("busco"),
+        merqury: args.is_present("merqury"),
+    })
+}
+
+// Renders templates for a matrix of options and lints every script
+fn check(base: &TemplateOptions) -> std::result::Result<(), std::io::Error> {
+    let mut combos = vec![];
+    for se in [false, true] {
+        for merge in [false, true] {
+            for quorum in [false, true] {
+                for unitigger in ["bcalm", "superreads", "tadpole"] {
+                    for extend in [false, true] {
+                        for busco in [false, true] {
+                            for queue in [false, true] {
+                                let mut opt = base.clone();
+                                opt.se = se;
+                                opt.merge = merge;
+                                opt.quorum = quorum;
+                                opt.unitigger = unitigger.to_string();
+                                opt.extend = extend;
+                                opt.busco = busco;
+                                if queue {
+                                    opt.queue = Some("mpi".to_string());
+                                }
+                                combos.push(opt);
+                            }
+                        }
+                    }
+                }
+            }
+        }
     }
-    if args.is_present("gatk") {
-        gen_gatk(&context)?;
+
+    // Identical scripts are linted once
+    let mut lint_of: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut n_problems = 0;
+    for opt in &combos {
+        let label = combo_label(opt);
+        let scripts = match opt.render_marked() {
+            Ok(scripts) => scripts,
+            Err(e) => {
+                println!("[{}] {}", label, e);
+                n_problems += 1;
+                continue;
+            }
+        };
+
+        for (outname, content) in scripts {
+            let problems = lint_of
+                .entry(content)
+                .or_insert_with_key(|content| lint_script(content));
+            for problem in problems.iter() {
+                println!("[{}] {}: {}", label, outname, problem);
+                n_problems += 1;
+            }
+        }
     }
 
-    if args.is_present("quorum") {
-        gen_quorum(&context)?;
-    } else {
-        gen_no_quorum(&context)?;
+    eprintln!(
+        "Checked {} distinct scripts of {} combinations, {} problems",
+        lint_of.len(),
+        combos.len(),
+        n_problems
+    );
+    if n_problems > 0 {
+        std::process::exit(1);
     }
-    gen_down_sampling(&context)?;
 
-    let unitiggers = args
-        .value_of("unitigger")
-        .unwrap()
-        .split_ascii_whitespace()
-        .collect_vec();
+    Ok(())
+}
 
-    for u in unitiggers.clone() {
-        gen_unitigs(&context, u)?;
+fn combo_label(opt: &TemplateOptions) -> String {
+    let mut label = vec![if opt.se { "se" } else { "pe" }.to_string()];
+    if opt.merge {
+        label.push("merge".to_string());
     }
-    gen_anchors(&context)?;
-    gen_stat_anchors(&context)?;
-
-    if !args.is_present("se") && args.is_present("merge") {
-        gen_merge(&context)?;
-        gen_mr_down_sampling(&context)?;
-        for u in unitiggers.clone() {
-            gen_mr_unitigs(&context, u)?;
-        }
-        gen_mr_anchors(&context)?;
-        gen_stat_mr_anchors(&context)?;
+    if opt.quorum {
+        label.push("quorum".to_string());
     }
-
-    gen_merge_anchors(&context)?;
-    gen_stat_merge_anchors(&context)?;
-
-    gen_spades(&context)?;
-    gen_megahit(&context)?;
-    gen_platanus(&context)?;
-    if !args.is_present("se") && args.is_present("merge") {
-        gen_mr_spades(&context)?;
-        gen_mr_megahit(&context)?;
+    label.push(opt.unitigger.clone());
+    if opt.extend {
+        label.push("extend".to_string());
     }
-    gen_stat_other_anchors(&context)?;
-
-    if args.is_present("extend") {
-        gen_glue_anchors(&context)?;
-        gen_fill_anchors(&context)?;
+    if opt.busco {
+        label.push("busco".to_string());
     }
-
-    gen_quast(&context)?;
-    gen_stat_final(&context)?;
-
-    if args.is_present("busco") {
-        gen_busco(&context)?;
+    if opt.queue.is_some() {
+        label.push("queue".to_string());
     }
 
-    gen_cleanup(&context)?;
-    gen_real_clean(&context)?;
-    gen_master(&context)?;
-    if args.is_present("queue") {
-        gen_bsub(&context)?;
+    label.join(" ")
+}
+
+// Inputs, outputs and tools of each script in run order
+fn plan(opt: &TemplateOptions) -> std::result::Result<(), std::io::Error> {
+    let items = opt.plan(Path::new("."))?;
+
+    let mut n_missing = 0;
+    for item in &items {
+        println!("{}", item.script);
+        for (path, status) in &item.inputs {
+            let status = match status {
+                InputStatus::From(script) => format!("from {}", script),
+                InputStatus::Present => "present".to_string(),
+                InputStatus::Absent => "absent, optional".to_string(),
+                InputStatus::Missing => {
+                    n_missing += 1;
+                    "MISSING".to_string()
+                }
+            };
+            println!("    < {} [{}]", path, status);
+        }
+        for path in &item.outputs {
+            println!("    > {}", path);
+        }
+        if !item.tools.is_empty() {
+            println!("    tools: {}", item.tools.join(" "));
+        }
     }
 
-    Ok(())
-}
-
-fn gen_fastqc(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "2_fastqc.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/2_fastqc.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_insert_size(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "2_insert_size.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/2_insert_size.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_kat(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "2_kat.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/2_kat.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_trim(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "2_trim.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/2_trim.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_stat_reads(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "9_stat_reads.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/9_stat_reads.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_quorum(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "2_quorum.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/2_quorum.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_no_quorum(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "2_quorum.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/2_no_quorum.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_merge(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "2_merge.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/2_merge.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_bwa(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "3_bwa.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/3_bwa.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_gatk(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "3_gatk.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/3_gatk.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_down_sampling(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "4_down_sampling.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/4_down_sampling.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_mr_down_sampling(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "6_down_sampling.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/6_down_sampling.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_unitigs(context: &Context, unitigger: &str) -> std::result::Result<(), std::io::Error> {
-    let outname = format!("4_unitigs_{}.sh", unitigger);
-    eprintln!("Create {}", outname);
-
-    let mut con = Context::new();
-    con.insert("outname", outname.as_str());
-    con.insert("unitigger", unitigger);
-    con.extend(context.clone());
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/4_unitigs.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &con).unwrap();
-    intspan::write_lines(outname.as_str(), &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_mr_unitigs(context: &Context, unitigger: &str) -> std::result::Result<(), std::io::Error> {
-    let outname = format!("6_unitigs_{}.sh", unitigger);
-    eprintln!("Create {}", outname);
-
-    let mut con = Context::new();
-    con.insert("outname", outname.as_str());
-    con.insert("unitigger", unitigger);
-    con.extend(context.clone());
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/6_unitigs.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &con).unwrap();
-    intspan::write_lines(outname.as_str(), &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_anchors(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "4_anchors.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/4_anchors.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_mr_anchors(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "6_anchors.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/6_anchors.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_stat_anchors(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "9_stat_anchors.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/9_stat_anchors.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_stat_mr_anchors(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "9_stat_mr_anchors.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        (
-            "t",
-            include_str!("../../templates/9_stat_mr_anchors.tera.sh"),
-        ),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_merge_anchors(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "7_merge_anchors.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/7_merge_anchors.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_stat_merge_anchors(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "9_stat_merge_anchors.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        (
-            "t",
-            include_str!("../../templates/9_stat_merge_anchors.tera.sh"),
-        ),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_spades(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "8_spades.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/8_spades.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_mr_spades(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "8_mr_spades.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/8_mr_spades.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_megahit(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "8_megahit.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/8_megahit.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_mr_megahit(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "8_mr_megahit.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/8_mr_megahit.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_platanus(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "8_platanus.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/8_platanus.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_stat_other_anchors(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "9_stat_other_anchors.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        (
-            "t",
-            include_str!("../../templates/9_stat_other_anchors.tera.sh"),
-        ),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_glue_anchors(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "7_glue_anchors.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/7_glue_anchors.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_fill_anchors(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "7_fill_anchors.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/7_fill_anchors.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_quast(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "9_quast.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/9_quast.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_busco(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "9_busco.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/9_busco.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_stat_final(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "9_stat_final.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/9_stat_final.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_cleanup(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "0_cleanup.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/0_cleanup.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_real_clean(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "0_real_clean.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/0_real_clean.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_master(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "0_master.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/0_master.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
-
-    Ok(())
-}
-
-fn gen_bsub(context: &Context) -> std::result::Result<(), std::io::Error> {
-    let outname = "0_bsub.sh";
-    eprintln!("Create {}", outname);
-
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/0_bsub.tera.sh")),
-    ])
-    .unwrap();
-
-    let rendered = tera.render("t", &context).unwrap();
-    intspan::write_lines(outname, &vec![rendered.as_str()])?;
+    eprintln!("{} scripts, {} missing inputs", items.len(), n_missing);
+    if n_missing > 0 {
+        std::process::exit(1);
+    }
 
     Ok(())
 }
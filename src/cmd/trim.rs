@@ -1,9 +1,8 @@
+use anchr::TrimOptions;
 use clap::*;
 use itertools::Itertools;
-use std::collections::HashMap;
+use std::env;
 use std::path::PathBuf;
-use std::{env, fs};
-use tera::{Context, Tera};
 
 // Create clap subcommand arguments
 pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
@@ -14,6 +13,14 @@ pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
 <file1> [file2]
 
 Fastq files can be gzipped
+
+--screen classifies reads against a local database after filtering
+    * kraken2, a directory of Kraken2 database
+    * mash, a sketch file (.msh) for `mash screen`
+    * Taxon proportions are written to <prefix>.screen.tsv
+    * With --taxon, reads assigned outside this taxon (a taxid or a name)
+      are removed. Unclassified reads and ones assigned to its ancestors are kept.
+      Only available for kraken2
 "#,
         )
         .arg(
@@ -127,6 +134,29 @@ Fastq files can be gzipped
                 .takes_value(true)
                 .empty_values(false),
         )
+        .arg(
+            Arg::with_name("screen")
+                .long("screen")
+                .help("The local database for contamination screening")
+                .takes_value(true)
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("screener")
+                .long("screener")
+                .help("kraken2 or mash")
+                .takes_value(true)
+                .possible_values(&["kraken2", "mash"])
+                .default_value("kraken2")
+                .empty_values(false),
+        )
+        .arg(
+            Arg::with_name("taxon")
+                .long("taxon")
+                .help("Remove reads assigned outside this taxon")
+                .takes_value(true)
+                .empty_values(false),
+        )
         .arg(
             Arg::with_name("xmx")
                 .long("xmx")
@@ -158,92 +188,35 @@ Fastq files can be gzipped
 pub fn execute(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
     let mut writer = intspan::writer(args.value_of("outfile").unwrap());
 
-    // context from args
-    let mut opt = HashMap::new();
-    opt.insert("qual", args.value_of("qual").unwrap());
-    opt.insert("len", args.value_of("len").unwrap());
-    opt.insert("filter", args.value_of("filter").unwrap());
-    opt.insert("trimq", args.value_of("trimq").unwrap());
-    opt.insert("trimk", args.value_of("trimk").unwrap());
-    opt.insert("matchk", args.value_of("matchk").unwrap());
-    opt.insert("cutk", args.value_of("cutk").unwrap());
-    opt.insert("prefix", args.value_of("prefix").unwrap());
-    opt.insert("dedupe", if args.is_present("dedupe") { "1" } else { "0" });
-    opt.insert("tile", if args.is_present("tile") { "1" } else { "0" });
-    opt.insert(
-        "cutoff",
-        if args.is_present("cutoff") {
-            args.value_of("cutoff").unwrap()
-        } else {
-            "0"
-        },
-    );
-    opt.insert(
-        "sample",
-        if args.is_present("sample") {
-            args.value_of("sample").unwrap()
-        } else {
-            "0"
-        },
-    );
-    opt.insert(
-        "xmx",
-        if args.is_present("xmx") {
-            args.value_of("xmx").unwrap()
-        } else {
-            "0"
-        },
-    );
-    opt.insert("parallel", args.value_of("parallel").unwrap());
+    // options from args
+    let mut opt = TrimOptions::new(&args.values_of("infiles").unwrap().collect_vec());
+    opt.qual = args.value_of("qual").unwrap().to_string();
+    opt.len = args.value_of("len").unwrap().to_string();
+    opt.filter = args.value_of("filter").unwrap().to_string();
+    opt.trimq = value_t!(args, "trimq", usize).unwrap_or_else(|e| e.exit());
+    opt.trimk = value_t!(args, "trimk", usize).unwrap_or_else(|e| e.exit());
+    opt.matchk = value_t!(args, "matchk", usize).unwrap_or_else(|e| e.exit());
+    opt.cutk = value_t!(args, "cutk", usize).unwrap_or_else(|e| e.exit());
+    opt.prefix = args.value_of("prefix").unwrap().to_string();
+    opt.dedupe = args.is_present("dedupe");
+    opt.tile = args.is_present("tile");
+    opt.cutoff = args.value_of("cutoff").map(|s| s.to_string());
+    opt.sample = args.value_of("sample").map(|s| s.to_string());
+    opt.screen = args.value_of("screen").map(|s| s.to_string());
+    opt.screener = args.value_of("screener").unwrap().to_string();
+    opt.taxon = args.value_of("taxon").map(|s| s.to_string());
+    opt.xmx = args.value_of("xmx").map(|s| s.to_string());
+    opt.parallel = value_t!(args, "parallel", usize).unwrap_or_else(|e| e.exit());
+    opt.adapter = args.value_of("adapter").map(PathBuf::from);
+    opt.artifact = args.value_of("artifact").map(PathBuf::from);
 
     // Default adapter and artifact files
-    let path = if args.is_present("adapter") {
-        PathBuf::from(args.value_of("adapter").unwrap())
-            .canonicalize()
-            .unwrap()
-    } else {
-        // write default adapter file
-        let file = "illumina_adapters.fa";
-        fs::write(file, include_str!("../../templates/illumina_adapters.fa"))?;
-        env::current_dir()?.join(file).canonicalize().unwrap()
-    };
-    opt.insert("adapter", path.to_str().unwrap());
-
-    let path = if args.is_present("artifact") {
-        PathBuf::from(args.value_of("artifact").unwrap())
-            .canonicalize()
-            .unwrap()
-    } else {
-        // write default adapter file
-        let file = "sequencing_artifacts.fa";
-        fs::write(
-            file,
-            include_str!("../../templates/sequencing_artifacts.fa"),
-        )?;
-        env::current_dir()?.join(file).canonicalize().unwrap()
-    };
-    opt.insert("artifact", path.to_str().unwrap());
-
-    let infiles = args.values_of("infiles").unwrap().collect_vec();
-
-    let mut context = Context::new();
-    context.insert("opt", &opt);
-    context.insert("args", &infiles);
-
-    // eprintln!("{:#?}", context);
-
-    // many templates
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("trim", include_str!("../../templates/trim.tera.sh")),
-    ])
-    .unwrap();
-
-    // eprintln!("{:#?}", tera);
-
-    let rendered = tera.render("trim", &context).unwrap();
+    opt.write_defaults(&env::current_dir()?)?;
 
+    let rendered = opt.render().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1)
+    });
     writer.write_all(rendered.as_ref())?;
 
     Ok(())
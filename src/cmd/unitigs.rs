@@ -1,7 +1,6 @@
+use anchr::UnitigsOptions;
 use clap::*;
 use itertools::Itertools;
-use std::collections::HashMap;
-use tera::{Context, Tera};
 
 // Create clap subcommand arguments
 pub fn make_subcommand<'a, 'b>() -> App<'a, 'b> {
@@ -84,37 +83,16 @@ Fasta files can't be gzipped
 pub fn execute(args: &ArgMatches) -> std::result::Result<(), std::io::Error> {
     let mut writer = intspan::writer(args.value_of("outfile").unwrap());
 
-    // context from args
-    let mut opt = HashMap::new();
-    opt.insert("unitigger", args.value_of("unitigger").unwrap());
-    opt.insert("estsize", args.value_of("estsize").unwrap());
-    opt.insert("kmer", args.value_of("kmer").unwrap());
-    opt.insert("min", args.value_of("min").unwrap());
+    // options from args
+    let mut opt = UnitigsOptions::new(&args.values_of("infiles").unwrap().collect_vec());
+    opt.unitigger = args.value_of("unitigger").unwrap().to_string();
+    opt.estsize = args.value_of("estsize").unwrap().to_string();
+    opt.kmer = args.value_of("kmer").unwrap().to_string();
+    opt.min = value_t!(args, "min", usize).unwrap_or_else(|e| e.exit());
+    opt.merge = args.is_present("merge");
+    opt.parallel = value_t!(args, "parallel", usize).unwrap_or_else(|e| e.exit());
 
-    opt.insert("merge", if args.is_present("merge") { "1" } else { "0" });
-    opt.insert("parallel", args.value_of("parallel").unwrap());
-
-    let infiles = args.values_of("infiles").unwrap().collect_vec();
-
-    let mut context = Context::new();
-    context.insert("opt", &opt);
-    context.insert("args", &infiles);
-
-    // eprintln!("{:#?}", context);
-
-    // many templates
-    let mut tera = Tera::default();
-    tera.add_raw_templates(vec![
-        ("header", include_str!("../../templates/header.tera.sh")),
-        ("t", include_str!("../../templates/unitigs.tera.sh")),
-    ])
-    .unwrap();
-
-    // eprintln!("{:#?}", tera);
-
-    let rendered = tera.render("t", &context).unwrap();
-
-    writer.write_all(rendered.as_ref())?;
+    writer.write_all(opt.render()?.as_ref())?;
 
     Ok(())
 }
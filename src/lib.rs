@@ -0,0 +1,20 @@
+//! Anchr - Assembler of N-free CHRomosomes
+//!
+//! Builders of the Bash scripts created by the `anchr` binary, so other tools can
+//! generate projects directly.
+//!
+//! ```no_run
+//! let mut opt = anchr::TemplateOptions::default();
+//! opt.genome = "4641652".to_string();
+//! opt.parallel = 24;
+//! opt.fastqc = true;
+//!
+//! opt.write_project(std::path::Path::new("mg1655"), None).unwrap();
+//! ```
+
+pub mod libs;
+
+pub use crate::libs::scripts::{
+    AnchorsOptions, MergeOptions, QuorumOptions, TrimOptions, UnitigsOptions,
+};
+pub use crate::libs::template::TemplateOptions;
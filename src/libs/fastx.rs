@@ -0,0 +1,223 @@
+//! FASTA and FASTQ records, shared by `anchr qtrim` and `anchr reads`.
+
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::ops::Range;
+use std::path::Path;
+
+/// A read
+pub struct Record {
+    /// The header line without `>` or `@`
+    pub name: String,
+    pub seq: Vec<u8>,
+    /// None for FASTA
+    pub qual: Option<Vec<u8>>,
+}
+
+impl Record {
+    /// Writes FASTQ, or FASTA when `fasta` is set or there are no qualities
+    pub fn write<W: Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        fasta: bool,
+    ) -> std::result::Result<(), std::io::Error> {
+        self.write_range(writer, fasta, 0..self.seq.len())
+    }
+
+    /// Writes a part of the read, e.g. a trimmed one
+    pub fn write_range<W: Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        fasta: bool,
+        range: Range<usize>,
+    ) -> std::result::Result<(), std::io::Error> {
+        match self.qual.as_ref() {
+            Some(qual) if !fasta => {
+                writer.write_all(b"@")?;
+                writer.write_all(self.name.as_bytes())?;
+                writer.write_all(b"\n")?;
+                writer.write_all(&self.seq[range.clone()])?;
+                writer.write_all(b"\n+\n")?;
+                writer.write_all(&qual[range])?;
+            }
+            _ => {
+                writer.write_all(b">")?;
+                writer.write_all(self.name.as_bytes())?;
+                writer.write_all(b"\n")?;
+                writer.write_all(&self.seq[range])?;
+            }
+        }
+        writer.write_all(b"\n")?;
+
+        Ok(())
+    }
+}
+
+/// Reads FASTA (sequences may span lines) and FASTQ records
+///
+/// Gzipped files may have multiple members, like those of bgzip or joined by `cat`
+///
+/// ```
+/// let mut reader = anchr::libs::fastx::Reader::new("tests/Lambda/head/R1.fq.gz").unwrap();
+/// let mut count = 0;
+/// while let Some(rec) = reader.read().unwrap() {
+///     assert_eq!(rec.seq.len(), rec.qual.unwrap().len());
+///     count += 1;
+/// }
+/// assert_eq!(count, 200);
+/// ```
+pub struct Reader {
+    infile: String,
+    reader: Box<dyn BufRead>,
+    // The header line of the next FASTA record
+    header: Option<String>,
+    line: String,
+}
+
+impl Reader {
+    /// `infile` ends in .gz for gzipped files. [stdin] for standard input
+    pub fn new(infile: &str) -> std::result::Result<Self, std::io::Error> {
+        let reader: Box<dyn BufRead> = if infile == "stdin" {
+            Box::new(BufReader::new(std::io::stdin()))
+        } else {
+            let file = File::open(infile).map_err(|e| {
+                std::io::Error::new(e.kind(), format!("Can't open {}: {}", infile, e))
+            })?;
+            if infile.ends_with(".gz") {
+                Box::new(BufReader::new(MultiGzDecoder::new(file)))
+            } else {
+                Box::new(BufReader::new(file))
+            }
+        };
+
+        Ok(Reader {
+            infile: infile.to_string(),
+            reader,
+            header: None,
+            line: String::new(),
+        })
+    }
+
+    // Returns the line without trailing whitespaces, or None at the end of the file
+    fn read_line(&mut self) -> std::result::Result<Option<String>, std::io::Error> {
+        self.line.clear();
+        if self.reader.read_line(&mut self.line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(self.line.trim_end().to_string()))
+    }
+
+    fn broken(&self, name: &str) -> std::io::Error {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Broken record {} of {}", name, self.infile),
+        )
+    }
+
+    /// The next record, or None at the end of the file
+    pub fn read(&mut self) -> std::result::Result<Option<Record>, std::io::Error> {
+        let header = match self.header.take() {
+            Some(header) => header,
+            None => loop {
+                match self.read_line()? {
+                    Some(line) if line.is_empty() => continue,
+                    Some(line) => break line,
+                    None => return Ok(None),
+                }
+            },
+        };
+
+        if let Some(name) = header.strip_prefix('>') {
+            let mut seq = vec![];
+            while let Some(line) = self.read_line()? {
+                if line.starts_with('>') {
+                    self.header = Some(line);
+                    break;
+                }
+                seq.extend_from_slice(line.as_bytes());
+            }
+
+            Ok(Some(Record {
+                name: name.to_string(),
+                seq,
+                qual: None,
+            }))
+        } else if let Some(name) = header.strip_prefix('@') {
+            let seq = self.read_line()?.ok_or_else(|| self.broken(name))?;
+            let plus = self.read_line()?.ok_or_else(|| self.broken(name))?;
+            let qual = self.read_line()?.ok_or_else(|| self.broken(name))?;
+            if !plus.starts_with('+') || seq.len() != qual.len() {
+                return Err(self.broken(name));
+            }
+
+            Ok(Some(Record {
+                name: name.to_string(),
+                seq: seq.into_bytes(),
+                qual: Some(qual.into_bytes()),
+            }))
+        } else {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} is neither FASTA nor FASTQ", self.infile),
+            ))
+        }
+    }
+}
+
+/// Writes plain or gzipped records
+///
+/// Call [`Writer::finish`] to see errors of the gzip trailer
+pub enum Writer {
+    Plain(Box<dyn Write>),
+    Gz(GzEncoder<BufWriter<File>>),
+}
+
+impl Writer {
+    /// Gzipped when `outfile` ends in .gz. [stdout] for screen
+    pub fn new(outfile: &str) -> std::result::Result<Self, std::io::Error> {
+        if outfile.ends_with(".gz") {
+            Writer::gz(Path::new(outfile))
+        } else if outfile == "stdout" {
+            Ok(Writer::Plain(Box::new(BufWriter::new(std::io::stdout()))))
+        } else {
+            Ok(Writer::Plain(Box::new(BufWriter::new(File::create(
+                outfile,
+            )?))))
+        }
+    }
+
+    /// Always gzipped, whatever the name is
+    pub fn gz(path: &Path) -> std::result::Result<Self, std::io::Error> {
+        let file = File::create(path)?;
+        Ok(Writer::Gz(GzEncoder::new(
+            BufWriter::new(file),
+            Compression::default(),
+        )))
+    }
+
+    pub fn finish(self) -> std::result::Result<(), std::io::Error> {
+        match self {
+            Writer::Plain(mut writer) => writer.flush(),
+            Writer::Gz(writer) => writer.finish()?.flush(),
+        }
+    }
+}
+
+impl Write for Writer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Writer::Plain(writer) => writer.write(buf),
+            Writer::Gz(writer) => writer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Writer::Plain(writer) => writer.flush(),
+            Writer::Gz(writer) => writer.flush(),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod fastx;
+pub mod scripts;
+pub mod template;
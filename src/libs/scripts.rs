@@ -0,0 +1,462 @@
+//! Builders of single scripts, the same as `anchr trim`, `anchr merge`, `anchr quorum`,
+//! `anchr unitigs` and `anchr anchors`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tera::{Context, Tera};
+
+/// Renders a template with `opt` and `args`
+fn render_script(
+    template: &str,
+    opt: &BTreeMap<&str, String>,
+    args: &[String],
+) -> std::result::Result<String, std::io::Error> {
+    let mut context = Context::new();
+    context.insert("opt", opt);
+    context.insert("args", args);
+
+    let mut tera = Tera::default();
+    tera.add_raw_templates(vec![
+        ("header", include_str!("../../templates/header.tera.sh")),
+        ("t", template),
+    ])
+    .unwrap();
+
+    tera.render("t", &context)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+}
+
+fn flag(b: bool) -> String {
+    if b { "1" } else { "0" }.to_string()
+}
+
+// Absent values are "0" in templates
+fn optional(v: &Option<String>) -> String {
+    v.clone().unwrap_or_else(|| "0".to_string())
+}
+
+pub(crate) fn invalid(msg: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, msg.to_string())
+}
+
+/// Options of `anchr trim`
+///
+/// ```
+/// let mut opt = anchr::TrimOptions::new(&["R1.fq.gz", "R2.fq.gz"]);
+/// opt.dedupe = true;
+/// assert!(opt.render().unwrap().contains("dedupe dupesubs=0"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TrimOptions {
+    /// R1 [R2] [Rs]
+    pub infiles: Vec<String>,
+    /// Quality thresholds, space separated
+    pub qual: String,
+    /// Length thresholds, space separated
+    pub len: String,
+    /// adapter, artifact, or both
+    pub filter: String,
+    pub trimq: usize,
+    pub trimk: usize,
+    pub matchk: usize,
+    pub cutk: usize,
+    /// `None` for the embedded illumina_adapters.fa
+    pub adapter: Option<PathBuf>,
+    /// `None` for the embedded sequencing_artifacts.fa
+    pub artifact: Option<PathBuf>,
+    pub prefix: String,
+    pub dedupe: bool,
+    pub tile: bool,
+    pub cutoff: Option<String>,
+    pub sample: Option<String>,
+    pub screen: Option<String>,
+    /// kraken2 or mash
+    pub screener: String,
+    pub taxon: Option<String>,
+    pub xmx: Option<String>,
+    pub parallel: usize,
+}
+
+impl Default for TrimOptions {
+    fn default() -> Self {
+        Self {
+            infiles: vec![],
+            qual: "25".to_string(),
+            len: "60".to_string(),
+            filter: "adapter".to_string(),
+            trimq: 15,
+            trimk: 23,
+            matchk: 27,
+            cutk: 31,
+            adapter: None,
+            artifact: None,
+            prefix: "R".to_string(),
+            dedupe: false,
+            tile: false,
+            cutoff: None,
+            sample: None,
+            screen: None,
+            screener: "kraken2".to_string(),
+            taxon: None,
+            xmx: None,
+            parallel: 8,
+        }
+    }
+}
+
+impl TrimOptions {
+    pub fn new(infiles: &[&str]) -> Self {
+        Self {
+            infiles: infiles.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Writes embedded adapter and artifact files into `dir` when they are not set
+    pub fn write_defaults(&mut self, dir: &Path) -> std::result::Result<(), std::io::Error> {
+        if self.adapter.is_none() {
+            let path = dir.join("illumina_adapters.fa");
+            fs::write(&path, include_str!("../../templates/illumina_adapters.fa"))?;
+            self.adapter = Some(path);
+        }
+        if self.artifact.is_none() {
+            let path = dir.join("sequencing_artifacts.fa");
+            fs::write(
+                &path,
+                include_str!("../../templates/sequencing_artifacts.fa"),
+            )?;
+            self.artifact = Some(path);
+        }
+
+        Ok(())
+    }
+
+    pub fn render(&self) -> std::result::Result<String, std::io::Error> {
+        if self.screener != "kraken2" && self.screener != "mash" {
+            return Err(invalid("--screener should be kraken2 or mash"));
+        }
+        if self.taxon.is_some() && self.screener != "kraken2" {
+            return Err(invalid("--taxon needs --screener kraken2"));
+        }
+
+        // Absolute paths are used in trim.sh
+        let abs = |path: &Option<PathBuf>, default: &str| {
+            path.as_ref()
+                .map(|p| p.canonicalize())
+                .unwrap_or_else(|| Ok(PathBuf::from(default)))
+        };
+        let adapter = abs(&self.adapter, "illumina_adapters.fa")?;
+        let artifact = abs(&self.artifact, "sequencing_artifacts.fa")?;
+
+        let mut opt = BTreeMap::new();
+        opt.insert("qual", self.qual.clone());
+        opt.insert("len", self.len.clone());
+        opt.insert("filter", self.filter.clone());
+        opt.insert("trimq", self.trimq.to_string());
+        opt.insert("trimk", self.trimk.to_string());
+        opt.insert("matchk", self.matchk.to_string());
+        opt.insert("cutk", self.cutk.to_string());
+        opt.insert("prefix", self.prefix.clone());
+        opt.insert("dedupe", flag(self.dedupe));
+        opt.insert("tile", flag(self.tile));
+        opt.insert("cutoff", optional(&self.cutoff));
+        opt.insert("sample", optional(&self.sample));
+        opt.insert("screen", optional(&self.screen));
+        opt.insert("screener", self.screener.clone());
+        opt.insert("taxon", optional(&self.taxon));
+        opt.insert("xmx", optional(&self.xmx));
+        opt.insert("parallel", self.parallel.to_string());
+        opt.insert("adapter", adapter.to_string_lossy().to_string());
+        opt.insert("artifact", artifact.to_string_lossy().to_string());
+
+        render_script(
+            include_str!("../../templates/trim.tera.sh"),
+            &opt,
+            &self.infiles,
+        )
+    }
+
+    /// Writes trim.sh and default adapter files into `dir`
+    pub fn write_project(&self, dir: &Path) -> std::result::Result<(), std::io::Error> {
+        fs::create_dir_all(dir)?;
+        let mut opt = self.clone();
+        opt.write_defaults(dir)?;
+        fs::write(dir.join("trim.sh"), opt.render()?)
+    }
+}
+
+/// Options of `anchr merge`
+#[derive(Debug, Clone)]
+pub struct MergeOptions {
+    /// R1 [R2] [Rs]
+    pub infiles: Vec<String>,
+    pub len: usize,
+    pub qual: usize,
+    pub prefilter: Option<String>,
+    /// Error-correct phases, space separated
+    pub ecphase: String,
+    pub prefixm: String,
+    pub prefixu: String,
+    pub xmx: Option<String>,
+    pub parallel: usize,
+}
+
+impl Default for MergeOptions {
+    fn default() -> Self {
+        Self {
+            infiles: vec![],
+            len: 60,
+            qual: 15,
+            prefilter: None,
+            ecphase: "1 2 3".to_string(),
+            prefixm: "M".to_string(),
+            prefixu: "U".to_string(),
+            xmx: None,
+            parallel: 8,
+        }
+    }
+}
+
+impl MergeOptions {
+    pub fn new(infiles: &[&str]) -> Self {
+        Self {
+            infiles: infiles.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn render(&self) -> std::result::Result<String, std::io::Error> {
+        let mut opt = BTreeMap::new();
+        opt.insert("len", self.len.to_string());
+        opt.insert("qual", self.qual.to_string());
+        opt.insert("prefilter", optional(&self.prefilter));
+        opt.insert("ecphase", self.ecphase.clone());
+        opt.insert("prefixm", self.prefixm.clone());
+        opt.insert("prefixu", self.prefixu.clone());
+        opt.insert("xmx", optional(&self.xmx));
+        opt.insert("parallel", self.parallel.to_string());
+
+        render_script(
+            include_str!("../../templates/merge.tera.sh"),
+            &opt,
+            &self.infiles,
+        )
+    }
+
+    /// Writes merge.sh into `dir`
+    pub fn write_project(&self, dir: &Path) -> std::result::Result<(), std::io::Error> {
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join("merge.sh"), self.render()?)
+    }
+}
+
+/// Options of `anchr quorum`
+#[derive(Debug, Clone)]
+pub struct QuorumOptions {
+    /// R1 [R2] [Rs]
+    pub infiles: Vec<String>,
+    /// Jellyfish hash size
+    pub jf: usize,
+    /// Estimated genome size, or auto
+    pub estsize: String,
+    pub prefix: String,
+    pub parallel: usize,
+}
+
+impl Default for QuorumOptions {
+    fn default() -> Self {
+        Self {
+            infiles: vec![],
+            jf: 500_000_000,
+            estsize: "auto".to_string(),
+            prefix: "pe".to_string(),
+            parallel: 8,
+        }
+    }
+}
+
+impl QuorumOptions {
+    pub fn new(infiles: &[&str]) -> Self {
+        Self {
+            infiles: infiles.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn render(&self) -> std::result::Result<String, std::io::Error> {
+        let mut opt = BTreeMap::new();
+        opt.insert("jf", self.jf.to_string());
+        opt.insert("estsize", self.estsize.clone());
+        opt.insert("prefix", self.prefix.clone());
+        opt.insert("parallel", self.parallel.to_string());
+
+        render_script(
+            include_str!("../../templates/quorum.tera.sh"),
+            &opt,
+            &self.infiles,
+        )
+    }
+
+    /// Writes quorum.sh into `dir`
+    pub fn write_project(&self, dir: &Path) -> std::result::Result<(), std::io::Error> {
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join("quorum.sh"), self.render()?)
+    }
+}
+
+/// Options of `anchr unitigs`
+#[derive(Debug, Clone)]
+pub struct UnitigsOptions {
+    /// <pe.cor.fa> <env.json>
+    pub infiles: Vec<String>,
+    /// superreads, tadpole, or bcalm
+    pub unitigger: String,
+    /// Estimated genome size, or auto
+    pub estsize: String,
+    /// K-mer sizes, space separated
+    pub kmer: String,
+    pub min: usize,
+    pub merge: bool,
+    pub parallel: usize,
+}
+
+impl Default for UnitigsOptions {
+    fn default() -> Self {
+        Self {
+            infiles: vec![],
+            unitigger: "superreads".to_string(),
+            estsize: "auto".to_string(),
+            kmer: "31".to_string(),
+            min: 1000,
+            merge: false,
+            parallel: 8,
+        }
+    }
+}
+
+impl UnitigsOptions {
+    pub fn new(infiles: &[&str]) -> Self {
+        Self {
+            infiles: infiles.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn render(&self) -> std::result::Result<String, std::io::Error> {
+        let mut opt = BTreeMap::new();
+        opt.insert("unitigger", self.unitigger.clone());
+        opt.insert("estsize", self.estsize.clone());
+        opt.insert("kmer", self.kmer.clone());
+        opt.insert("min", self.min.to_string());
+        opt.insert("merge", flag(self.merge));
+        opt.insert("parallel", self.parallel.to_string());
+
+        render_script(
+            include_str!("../../templates/unitigs.tera.sh"),
+            &opt,
+            &self.infiles,
+        )
+    }
+
+    /// Writes unitigs.sh into `dir`
+    pub fn write_project(&self, dir: &Path) -> std::result::Result<(), std::io::Error> {
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join("unitigs.sh"), self.render()?)
+    }
+}
+
+/// Options of `anchr anchors`
+///
+/// ```
+/// let mut opt = anchr::AnchorsOptions::new(&["contig.fasta", "pe.cor.fa"]);
+/// opt.organelle = true;
+/// opt.gc = Some((0.2, 0.45));
+/// assert!(opt.render().unwrap().contains("organelle.fasta"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnchorsOptions {
+    /// <contig.fasta> <pe.cor.fa> [more reads]
+    pub infiles: Vec<String>,
+    pub min: usize,
+    pub mincov: usize,
+    pub readl: usize,
+    pub mscale: f64,
+    pub lscale: f64,
+    pub uscale: f64,
+    pub fill: usize,
+    pub ratio: f64,
+    pub longest: bool,
+    pub keepedge: bool,
+    pub organelle: bool,
+    pub orgratio: f64,
+    /// Range of GC content of organelles
+    pub gc: Option<(f64, f64)>,
+    pub parallel: usize,
+}
+
+impl Default for AnchorsOptions {
+    fn default() -> Self {
+        Self {
+            infiles: vec![],
+            min: 1000,
+            mincov: 5,
+            readl: 100,
+            mscale: 3.0,
+            lscale: 3.0,
+            uscale: 2.0,
+            fill: 1,
+            ratio: 0.98,
+            longest: false,
+            keepedge: false,
+            organelle: false,
+            orgratio: 5.0,
+            gc: None,
+            parallel: 8,
+        }
+    }
+}
+
+impl AnchorsOptions {
+    pub fn new(infiles: &[&str]) -> Self {
+        Self {
+            infiles: infiles.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn render(&self) -> std::result::Result<String, std::io::Error> {
+        let mut opt = BTreeMap::new();
+        opt.insert("min", self.min.to_string());
+        opt.insert("mincov", self.mincov.to_string());
+        opt.insert("readl", self.readl.to_string());
+        opt.insert("mscale", self.mscale.to_string());
+        opt.insert("lscale", self.lscale.to_string());
+        opt.insert("uscale", self.uscale.to_string());
+        opt.insert("ratio", self.ratio.to_string());
+        opt.insert("fill", self.fill.to_string());
+        opt.insert("longest", flag(self.longest));
+        opt.insert("keepedge", flag(self.keepedge));
+        opt.insert("organelle", flag(self.organelle));
+        opt.insert("orgratio", self.orgratio.to_string());
+        let (gc_min, gc_max) = match self.gc {
+            Some((min, max)) => (min.to_string(), max.to_string()),
+            None => ("0".to_string(), "0".to_string()),
+        };
+        opt.insert("gc_min", gc_min);
+        opt.insert("gc_max", gc_max);
+        opt.insert("parallel", self.parallel.to_string());
+
+        render_script(
+            include_str!("../../templates/anchors.tera.sh"),
+            &opt,
+            &self.infiles,
+        )
+    }
+
+    /// Writes anchors.sh into `dir`
+    pub fn write_project(&self, dir: &Path) -> std::result::Result<(), std::io::Error> {
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join("anchors.sh"), self.render()?)
+    }
+}
@@ -0,0 +1,1466 @@
+//! Builder of a whole project, the same as `anchr template`.
+
+use super::scripts::invalid;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use tera::{Context, Tera};
+
+/// All templates embedded in this crate, name => content
+pub const TEMPLATES: &[(&str, &str)] = &[
+    ("header", include_str!("../../templates/header.tera.sh")),
+    (
+        "user_hooks",
+        include_str!("../../templates/user_hooks.tera.sh"),
+    ),
+    ("2_fastqc", include_str!("../../templates/2_fastqc.tera.sh")),
+    (
+        "2_insert_size",
+        include_str!("../../templates/2_insert_size.tera.sh"),
+    ),
+    ("2_kat", include_str!("../../templates/2_kat.tera.sh")),
+    ("2_trim", include_str!("../../templates/2_trim.tera.sh")),
+    (
+        "2_kmer_profile",
+        include_str!("../../templates/2_kmer_profile.tera.sh"),
+    ),
+    ("trim", include_str!("../../templates/trim.tera.sh")),
+    (
+        "9_stat_reads",
+        include_str!("../../templates/9_stat_reads.tera.sh"),
+    ),
+    ("2_quorum", include_str!("../../templates/2_quorum.tera.sh")),
+    (
+        "2_no_quorum",
+        include_str!("../../templates/2_no_quorum.tera.sh"),
+    ),
+    ("quorum", include_str!("../../templates/quorum.tera.sh")),
+    ("2_merge", include_str!("../../templates/2_merge.tera.sh")),
+    ("merge", include_str!("../../templates/merge.tera.sh")),
+    ("3_bwa", include_str!("../../templates/3_bwa.tera.sh")),
+    ("3_gatk", include_str!("../../templates/3_gatk.tera.sh")),
+    (
+        "3_freebayes",
+        include_str!("../../templates/3_freebayes.tera.sh"),
+    ),
+    ("3_long", include_str!("../../templates/3_long.tera.sh")),
+    (
+        "4_down_sampling",
+        include_str!("../../templates/4_down_sampling.tera.sh"),
+    ),
+    (
+        "6_down_sampling",
+        include_str!("../../templates/6_down_sampling.tera.sh"),
+    ),
+    (
+        "4_unitigs",
+        include_str!("../../templates/4_unitigs.tera.sh"),
+    ),
+    (
+        "6_unitigs",
+        include_str!("../../templates/6_unitigs.tera.sh"),
+    ),
+    ("unitigs", include_str!("../../templates/unitigs.tera.sh")),
+    (
+        "4_anchors",
+        include_str!("../../templates/4_anchors.tera.sh"),
+    ),
+    (
+        "6_anchors",
+        include_str!("../../templates/6_anchors.tera.sh"),
+    ),
+    ("anchors", include_str!("../../templates/anchors.tera.sh")),
+    (
+        "9_stat_anchors",
+        include_str!("../../templates/9_stat_anchors.tera.sh"),
+    ),
+    (
+        "9_stat_mr_anchors",
+        include_str!("../../templates/9_stat_mr_anchors.tera.sh"),
+    ),
+    (
+        "7_merge_anchors",
+        include_str!("../../templates/7_merge_anchors.tera.sh"),
+    ),
+    (
+        "9_stat_merge_anchors",
+        include_str!("../../templates/9_stat_merge_anchors.tera.sh"),
+    ),
+    ("8_spades", include_str!("../../templates/8_spades.tera.sh")),
+    (
+        "8_mr_spades",
+        include_str!("../../templates/8_mr_spades.tera.sh"),
+    ),
+    (
+        "8_megahit",
+        include_str!("../../templates/8_megahit.tera.sh"),
+    ),
+    (
+        "8_mr_megahit",
+        include_str!("../../templates/8_mr_megahit.tera.sh"),
+    ),
+    (
+        "8_platanus",
+        include_str!("../../templates/8_platanus.tera.sh"),
+    ),
+    ("8_flye", include_str!("../../templates/8_flye.tera.sh")),
+    ("8_canu", include_str!("../../templates/8_canu.tera.sh")),
+    (
+        "9_stat_meta",
+        include_str!("../../templates/9_stat_meta.tera.sh"),
+    ),
+    (
+        "9_stat_other_anchors",
+        include_str!("../../templates/9_stat_other_anchors.tera.sh"),
+    ),
+    (
+        "7_glue_anchors",
+        include_str!("../../templates/7_glue_anchors.tera.sh"),
+    ),
+    (
+        "7_fill_anchors",
+        include_str!("../../templates/7_fill_anchors.tera.sh"),
+    ),
+    ("7_polish", include_str!("../../templates/7_polish.tera.sh")),
+    ("9_quast", include_str!("../../templates/9_quast.tera.sh")),
+    ("9_busco", include_str!("../../templates/9_busco.tera.sh")),
+    (
+        "9_merqury",
+        include_str!("../../templates/9_merqury.tera.sh"),
+    ),
+    (
+        "9_stat_final",
+        include_str!("../../templates/9_stat_final.tera.sh"),
+    ),
+    (
+        "9_stat_variants",
+        include_str!("../../templates/9_stat_variants.tera.sh"),
+    ),
+    (
+        "0_cleanup",
+        include_str!("../../templates/0_cleanup.tera.sh"),
+    ),
+    (
+        "0_real_clean",
+        include_str!("../../templates/0_real_clean.tera.sh"),
+    ),
+    ("0_master", include_str!("../../templates/0_master.tera.sh")),
+    ("0_bsub", include_str!("../../templates/0_bsub.tera.sh")),
+    ("0_batch", include_str!("../../templates/0_batch.tera.sh")),
+    (
+        "9_stat_batch",
+        include_str!("../../templates/9_stat_batch.tera.sh"),
+    ),
+];
+
+/// A stage of the pipeline
+pub struct Stage {
+    /// Name of the stage, also the script name without `.sh`
+    pub name: &'static str,
+    /// Name of the embedded template
+    pub template: &'static str,
+    /// One script per unitigger, e.g. 4_unitigs_bcalm.sh
+    pub per_unitigger: bool,
+    /// Whether the stage is part of the project
+    pub when: fn(&TemplateOptions) -> bool,
+    /// Files read by the stage, see [`PlanItem`]
+    pub inputs: &'static [&'static str],
+    /// Primary files or directories written by the stage
+    pub outputs: &'static [&'static str],
+    /// Run by hand, not by 0_master.sh and 0_bsub.sh
+    pub manual: bool,
+}
+
+impl Stage {
+    const fn io(self, inputs: &'static [&'static str], outputs: &'static [&'static str]) -> Self {
+        Stage {
+            inputs,
+            outputs,
+            ..self
+        }
+    }
+
+    const fn manual(self) -> Self {
+        Stage {
+            manual: true,
+            ..self
+        }
+    }
+}
+
+const fn stage(
+    name: &'static str,
+    template: &'static str,
+    per_unitigger: bool,
+    when: fn(&TemplateOptions) -> bool,
+) -> Stage {
+    Stage {
+        name,
+        template,
+        per_unitigger,
+        when,
+        inputs: &[],
+        outputs: &[],
+        manual: false,
+    }
+}
+
+fn always(_: &TemplateOptions) -> bool {
+    true
+}
+
+fn single(o: &TemplateOptions) -> bool {
+    !o.meta
+}
+
+fn mr(o: &TemplateOptions) -> bool {
+    !o.se && o.merge
+}
+
+fn mr_single(o: &TemplateOptions) -> bool {
+    !o.se && o.merge && !o.meta
+}
+
+/// All stages in the order of creation
+pub const STAGES: &[Stage] = &[
+    stage("2_fastqc", "2_fastqc", false, |o| o.fastqc).io(
+        &["2_illumina/R1.fq.gz", "!se:2_illumina/R2.fq.gz"],
+        &["2_illumina/fastqc/"],
+    ),
+    stage("2_insert_size", "2_insert_size", false, |o| o.insertsize).io(
+        &[
+            "2_illumina/R1.fq.gz",
+            "!se:2_illumina/R2.fq.gz",
+            "?1_genome/genome.fa",
+        ],
+        &["statInsertSize.md"],
+    ),
+    stage("2_kat", "2_kat", false, |o| o.kat).io(
+        &["2_illumina/R1.fq.gz", "!se:2_illumina/R2.fq.gz"],
+        &["statKAT.md", "statKAT.tsv"],
+    ),
+    stage("2_trim", "2_trim", false, always).io(
+        &["2_illumina/R1.fq.gz", "!se:2_illumina/R2.fq.gz"],
+        &["2_illumina/trim/", "2_illumina/Q*L*/", "statTrimReads.md"],
+    ),
+    stage("2_kmer_profile", "2_kmer_profile", false, |o| {
+        o.genome == "auto"
+    })
+    .io(&["2_illumina/trim/"], &["statKmerProfile.md"]),
+    stage("9_stat_reads", "9_stat_reads", false, always).io(
+        &[
+            "2_illumina/Q*L*/",
+            "?1_genome/genome.fa",
+            "long:3_long/long.fasta",
+        ],
+        &["statReads.md", "statReads.tsv"],
+    ),
+    stage("3_long", "3_long", false, |o| o.long()).io(&["{long}"], &["3_long/long.fasta"]),
+    stage("3_bwa", "3_bwa", false, |o| o.bwa.is_some()).io(
+        &["1_genome/genome.fa", "2_illumina/{bwa}/R1.fq.gz"],
+        &["3_bwa/"],
+    ),
+    stage("3_gatk", "3_gatk", false, |o| o.gatk()).io(&["3_bwa/"], &["3_gatk/"]),
+    stage("3_freebayes", "3_freebayes", false, |o| o.freebayes())
+        .io(&["3_bwa/"], &["3_freebayes/"]),
+    stage("9_stat_variants", "9_stat_variants", false, |o| {
+        o.gatk() || o.freebayes()
+    })
+    .io(&["3_bwa/"], &["statVariants.md", "statVariants.tsv"]),
+    stage("2_quorum", "2_quorum", false, |o| o.quorum)
+        .io(&["2_illumina/Q*L*/"], &["2_illumina/Q*L*/pe.cor.fa.gz"]),
+    stage("2_quorum", "2_no_quorum", false, |o| !o.quorum)
+        .io(&["2_illumina/Q*L*/"], &["2_illumina/Q*L*/pe.cor.fa.gz"]),
+    stage("4_down_sampling", "4_down_sampling", false, single)
+        .io(&["2_illumina/Q*L*/pe.cor.fa.gz"], &["4_down_sampling/"]),
+    stage("4_unitigs", "4_unitigs", true, single)
+        .io(&["4_down_sampling/"], &["4_unitigs_{unitigger}/"]),
+    stage("4_anchors", "4_anchors", false, single)
+        .io(&["4_unitigs_*/"], &["4_unitigs_*/*/anchor/"]),
+    stage("9_stat_anchors", "9_stat_anchors", false, single).io(
+        &["4_unitigs_*/*/anchor/"],
+        &["statAnchors.md", "statAnchors.tsv"],
+    ),
+    stage("2_merge", "2_merge", false, mr).io(
+        &["2_illumina/trim/"],
+        &["2_illumina/merge/pe.cor.fa.gz", "statMergeReads.md"],
+    ),
+    stage("6_down_sampling", "6_down_sampling", false, mr_single)
+        .io(&["2_illumina/merge/pe.cor.fa.gz"], &["6_down_sampling/"]),
+    stage("6_unitigs", "6_unitigs", true, mr_single)
+        .io(&["6_down_sampling/"], &["6_unitigs_{unitigger}/"]),
+    stage("6_anchors", "6_anchors", false, mr_single)
+        .io(&["6_unitigs_*/"], &["6_unitigs_*/*/anchor/"]),
+    stage("9_stat_mr_anchors", "9_stat_mr_anchors", false, mr_single).io(
+        &["6_unitigs_*/*/anchor/"],
+        &["statMRAnchors.md", "statMRAnchors.tsv"],
+    ),
+    stage("7_merge_anchors", "7_merge_anchors", false, single).io(
+        &["4_unitigs_*/*/anchor/", "2_illumina/trim/pe.cor.fa.gz"],
+        &["7_merge_anchors/anchor.merge.fasta"],
+    ),
+    stage(
+        "9_stat_merge_anchors",
+        "9_stat_merge_anchors",
+        false,
+        single,
+    )
+    .io(
+        &["7_merge_anchors/"],
+        &["statMergeAnchors.md", "statMergeAnchors.tsv"],
+    ),
+    stage("8_spades", "8_spades", false, always).io(
+        &["2_illumina/trim/pe.cor.fa.gz"],
+        &["8_spades/anchor/anchor.fasta"],
+    ),
+    stage("8_megahit", "8_megahit", false, always).io(
+        &["2_illumina/trim/pe.cor.fa.gz"],
+        &["8_megahit/anchor/anchor.fasta"],
+    ),
+    stage("8_platanus", "8_platanus", false, single).io(
+        &["2_illumina/trim/pe.cor.fa.gz"],
+        &["8_platanus/anchor/anchor.fasta"],
+    ),
+    stage("8_flye", "8_flye", false, |o| o.long())
+        .io(&["3_long/long.fasta"], &["8_flye/anchor/anchor.fasta"]),
+    stage("8_canu", "8_canu", false, |o| o.long() && o.canu && !o.meta)
+        .io(&["3_long/long.fasta"], &["8_canu/anchor/anchor.fasta"]),
+    stage("8_mr_spades", "8_mr_spades", false, mr).io(
+        &["2_illumina/merge/pe.cor.fa.gz"],
+        &["8_mr_spades/anchor/anchor.fasta"],
+    ),
+    stage("8_mr_megahit", "8_mr_megahit", false, mr).io(
+        &["2_illumina/merge/pe.cor.fa.gz"],
+        &["8_mr_megahit/anchor/anchor.fasta"],
+    ),
+    stage("9_stat_meta", "9_stat_meta", false, |o| o.meta).io(
+        &["8_spades/", "8_megahit/"],
+        &["9_meta/", "statMeta.md", "statMeta.tsv"],
+    ),
+    stage(
+        "9_stat_other_anchors",
+        "9_stat_other_anchors",
+        false,
+        single,
+    )
+    .io(
+        &["8_spades/anchor/anchor.fasta"],
+        &["statOtherAnchors.md", "statOtherAnchors.tsv"],
+    ),
+    stage("7_glue_anchors", "7_glue_anchors", false, |o| {
+        !o.meta && (o.extend || o.long())
+    })
+    .io(
+        &["7_merge_anchors/anchor.merge.fasta"],
+        &["7_glue_anchors/contig.fasta"],
+    ),
+    stage("7_fill_anchors", "7_fill_anchors", false, |o| {
+        !o.meta && (o.extend || o.long())
+    })
+    .io(
+        &["7_glue_anchors/contig.fasta"],
+        &["7_fill_anchors/contig.fasta"],
+    ),
+    stage("7_polish", "7_polish", false, |o| !o.meta && o.polish).io(
+        &["7_merge_anchors/anchor.merge.fasta"],
+        &["7_polish/contig.fasta", "statPolish.md", "statPolish.tsv"],
+    ),
+    stage("9_quast", "9_quast", false, always).io(&["?1_genome/genome.fa"], &["9_quast/"]),
+    stage("9_stat_final", "9_stat_final", false, always).io(
+        &["?1_genome/genome.fa", "?statMerqury.tsv"],
+        &["statFinal.md", "statFinal.tsv"],
+    ),
+    stage("9_busco", "9_busco", false, |o| o.busco)
+        .io(&["?1_genome/genome.fa"], &["9_busco/", "statBusco.md"])
+        .manual(),
+    stage("9_merqury", "9_merqury", false, |o| o.merqury).io(
+        &["2_illumina/trim/"],
+        &["9_merqury/", "statMerqury.md", "statMerqury.tsv"],
+    ),
+    stage("0_cleanup", "0_cleanup", false, always).manual(),
+    stage("0_real_clean", "0_real_clean", false, always).manual(),
+    stage("0_master", "0_master", false, always).manual(),
+    stage("0_bsub", "0_bsub", false, |o| o.queue.is_some()).manual(),
+];
+
+/// A stage from `NAME.tera.sh` of the user template directory
+///
+/// The first line of the template is a Tera comment of metadata
+///
+/// ```text
+/// {# anchr: after=9_stat_final when=merge,!meta per_unitigger #}
+/// ```
+///
+/// * after, the script is created after this stage, otherwise before 0_cleanup
+/// * when, keys of `opt` that should be set, or unset with a leading `!`. Other keys are errors
+/// * per_unitigger, one script per unitigger
+/// * inputs and outputs, comma separated paths for `anchr template --plan`
+///
+/// A template with the name of a built-in stage replaces the embedded one.
+/// Other stages run right after their `after` stage in 0_master.sh and 0_bsub.sh,
+/// or at the end without it
+#[derive(Debug, Clone, Default)]
+pub struct UserStage {
+    pub name: String,
+    pub content: String,
+    pub after: Option<String>,
+    pub when: Vec<String>,
+    pub per_unitigger: bool,
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+}
+
+impl UserStage {
+    pub fn parse(name: &str, content: &str) -> std::result::Result<Self, std::io::Error> {
+        let mut stage = UserStage {
+            name: name.to_string(),
+            content: content.to_string(),
+            ..Default::default()
+        };
+
+        let first = content.lines().next().unwrap_or("").trim();
+        let meta = match first
+            .strip_prefix("{#")
+            .and_then(|s| s.strip_suffix("#}"))
+            .and_then(|s| s.trim().strip_prefix("anchr:"))
+        {
+            Some(meta) => meta,
+            None => return Ok(stage),
+        };
+
+        for field in meta.split_ascii_whitespace() {
+            let (key, value) = match field.find('=') {
+                Some(i) => (&field[..i], &field[i + 1..]),
+                None => (field, ""),
+            };
+            match key {
+                "after" => stage.after = Some(value.to_string()),
+                "when" => {
+                    stage.when = split_list(value);
+                    // a key missing from `opt` would never be set
+                    let keys = TemplateOptions::default().opt();
+                    if stage
+                        .when
+                        .iter()
+                        .any(|cond| !keys.contains_key(cond.trim_start_matches('!')))
+                    {
+                        return Err(invalid(&format!(
+                            "Unknown metadata {} of the template {}",
+                            field, name
+                        )));
+                    }
+                }
+                "inputs" => stage.inputs = split_list(value),
+                "outputs" => stage.outputs = split_list(value),
+                "per_unitigger" => stage.per_unitigger = true,
+                _ => {
+                    return Err(invalid(&format!(
+                        "Unknown metadata {} of the template {}",
+                        field, name
+                    )))
+                }
+            }
+        }
+
+        Ok(stage)
+    }
+
+    fn enabled(&self, opt: &BTreeMap<&'static str, String>) -> bool {
+        self.when.iter().all(|cond| condition(opt, cond))
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Reads `*.tera.sh` in `dir`, sorted by names
+pub fn read_user_stages(dir: &Path) -> std::result::Result<Vec<UserStage>, std::io::Error> {
+    let mut paths: Vec<_> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .collect();
+    paths.sort();
+
+    let mut stages = vec![];
+    for path in paths {
+        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+        if let Some(name) = file_name.strip_suffix(".tera.sh") {
+            let content = fs::read_to_string(&path)?;
+            stages.push(UserStage::parse(name, &content)?);
+        }
+    }
+
+    Ok(stages)
+}
+
+/// Tool => image. Blank lines and lines starting with `#` are skipped
+pub fn read_images(input: &str) -> BTreeMap<String, String> {
+    let mut images: BTreeMap<String, String> = BTreeMap::new();
+    for (tool, image) in intspan::read_replaces(input) {
+        if tool.is_empty() || tool.starts_with('#') {
+            continue;
+        }
+        if let Some(image) = image.first() {
+            images.insert(tool, image.to_string());
+        }
+    }
+
+    images
+}
+
+/// Options of `anchr template`
+///
+/// ```
+/// let mut opt = anchr::TemplateOptions::default();
+/// opt.genome = "4641652".to_string();
+/// opt.merge = true;
+///
+/// let scripts = opt.render().unwrap();
+/// assert!(scripts.iter().any(|(name, _)| name == "2_merge.sh"));
+/// assert!(scripts.iter().any(|(name, _)| name == "0_master.sh"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TemplateOptions {
+    /// Genome size, or auto to estimate it from k-mers
+    pub genome: String,
+    pub se: bool,
+    pub meta: bool,
+    pub xmx: Option<String>,
+    pub parallel: usize,
+    pub queue: Option<String>,
+    /// docker, singularity or apptainer
+    pub container: Option<String>,
+    /// Path of the map of tools to images
+    pub images: Option<String>,
+    /// Directory of user templates, see [`UserStage`]
+    pub templates: Option<String>,
+    pub strict: bool,
+    pub retries: usize,
+
+    pub fastqc: bool,
+    pub kat: bool,
+    pub insertsize: bool,
+    pub reads: usize,
+
+    /// Other options of `anchr trim`
+    pub trim: String,
+    pub sample: Option<String>,
+    /// Quality thresholds, space separated
+    pub qual: String,
+    /// Length thresholds, space separated
+    pub len: String,
+    pub filter: String,
+    pub screen: Option<String>,
+    /// kraken2 or mash
+    pub screener: String,
+    pub taxon: Option<String>,
+
+    pub quorum: bool,
+    pub merge: bool,
+    pub prefilter: Option<String>,
+    pub ecphase: String,
+
+    pub bwa: Option<String>,
+    /// gatk, freebayes or both
+    pub caller: Option<String>,
+
+    pub ont: Option<String>,
+    pub pacbio: Option<String>,
+    pub longmin: usize,
+    pub longidt: f64,
+    pub canu: bool,
+
+    /// Coverages of down sampling, space separated
+    pub cov: String,
+    /// Unitiggers, space separated
+    pub unitigger: String,
+    pub splitp: usize,
+    pub statp: usize,
+    pub readl: usize,
+    pub uscale: f64,
+    pub lscale: f64,
+    pub redo: bool,
+    pub organelle: bool,
+
+    pub extend: bool,
+    pub gluemin: usize,
+    pub fillmax: usize,
+
+    pub polish: bool,
+    /// pilon or racon
+    pub polisher: String,
+    pub rounds: usize,
+
+    pub busco: bool,
+    pub merqury: bool,
+}
+
+impl Default for TemplateOptions {
+    fn default() -> Self {
+        Self {
+            genome: "1000000".to_string(),
+            se: false,
+            meta: false,
+            xmx: None,
+            parallel: 8,
+            queue: None,
+            container: None,
+            images: None,
+            templates: None,
+            strict: false,
+            retries: 0,
+            fastqc: false,
+            kat: false,
+            insertsize: false,
+            reads: 1_000_000,
+            trim: "--dedupe".to_string(),
+            sample: None,
+            qual: "25 30".to_string(),
+            len: "60".to_string(),
+            filter: "adapter".to_string(),
+            screen: None,
+            screener: "kraken2".to_string(),
+            taxon: None,
+            quorum: false,
+            merge: false,
+            prefilter: None,
+            ecphase: "1 2 3".to_string(),
+            bwa: None,
+            caller: None,
+            ont: None,
+            pacbio: None,
+            longmin: 1000,
+            longidt: 0.85,
+            canu: false,
+            cov: "40 80".to_string(),
+            unitigger: "bcalm".to_string(),
+            splitp: 20,
+            statp: 2,
+            readl: 100,
+            uscale: 2.0,
+            lscale: 3.0,
+            redo: false,
+            organelle: false,
+            extend: false,
+            gluemin: 30,
+            fillmax: 100,
+            polish: false,
+            polisher: "pilon".to_string(),
+            rounds: 2,
+            busco: false,
+            merqury: false,
+        }
+    }
+}
+
+fn flag(b: bool) -> String {
+    if b { "1" } else { "0" }.to_string()
+}
+
+// Absent values are "0" in templates
+fn optional(v: &Option<String>) -> String {
+    v.clone().unwrap_or_else(|| "0".to_string())
+}
+
+impl TemplateOptions {
+    fn long(&self) -> bool {
+        self.ont.is_some() || self.pacbio.is_some()
+    }
+
+    fn gatk(&self) -> bool {
+        matches!(self.caller.as_deref(), Some("gatk") | Some("both"))
+    }
+
+    fn freebayes(&self) -> bool {
+        matches!(self.caller.as_deref(), Some("freebayes") | Some("both"))
+    }
+
+    fn check(&self) -> std::result::Result<(), std::io::Error> {
+        if let Some(container) = &self.container {
+            if !["docker", "singularity", "apptainer"].contains(&container.as_str()) {
+                return Err(invalid(
+                    "--container should be docker, singularity or apptainer",
+                ));
+            }
+        }
+        if self.screener != "kraken2" && self.screener != "mash" {
+            return Err(invalid("--screener should be kraken2 or mash"));
+        }
+        if self.taxon.is_some() && self.screener != "kraken2" {
+            return Err(invalid("--taxon needs --screener kraken2"));
+        }
+        if let Some(caller) = &self.caller {
+            if !["gatk", "freebayes", "both"].contains(&caller.as_str()) {
+                return Err(invalid("--caller should be gatk, freebayes or both"));
+            }
+        }
+        if self.polisher != "pilon" && self.polisher != "racon" {
+            return Err(invalid("--polisher should be pilon or racon"));
+        }
+        // The genome size is unknown when 2_trim.sh down-samples reads
+        if self.sample.is_some() && self.genome == "auto" {
+            return Err(invalid("--sample needs a genome size, not --genome auto"));
+        }
+
+        Ok(())
+    }
+
+    /// Values of `opt` in templates
+    pub fn opt(&self) -> BTreeMap<&'static str, String> {
+        let mut opt = BTreeMap::new();
+
+        // --genome auto is replaced by the result of 2_kmer_profile.sh at runtime
+        let genome_auto = self.genome == "auto";
+        opt.insert(
+            "genome",
+            if genome_auto {
+                "${GENOME_SIZE}".to_string()
+            } else {
+                self.genome.clone()
+            },
+        );
+        opt.insert("genome_auto", flag(genome_auto));
+        opt.insert("se", flag(self.se));
+        // Coverage-based anchors assume a single genome
+        opt.insert("meta", flag(self.meta));
+        opt.insert("xmx", optional(&self.xmx));
+        opt.insert("parallel", self.parallel.to_string());
+        opt.insert("queue", optional(&self.queue));
+
+        opt.insert("container", optional(&self.container));
+        opt.insert("images", optional(&self.images));
+
+        opt.insert("strict", flag(self.strict));
+        opt.insert("retries", self.retries.to_string());
+
+        opt.insert("fastqc", flag(self.fastqc));
+        opt.insert("kat", flag(self.kat));
+        opt.insert("insertsize", flag(self.insertsize));
+        opt.insert("reads", self.reads.to_string());
+
+        opt.insert("trim", self.trim.clone());
+        // k-mer size of histograms in 2_trim.sh
+        opt.insert(
+            "cutk",
+            self.trim
+                .split_ascii_whitespace()
+                .skip_while(|s| *s != "--cutk")
+                .nth(1)
+                .unwrap_or("31")
+                .to_string(),
+        );
+        opt.insert("sample", optional(&self.sample));
+        opt.insert("qual", self.qual.clone());
+        opt.insert("len", self.len.clone());
+        opt.insert("filter", self.filter.clone());
+        opt.insert("screen", optional(&self.screen));
+        opt.insert("screener", self.screener.clone());
+        opt.insert("taxon", optional(&self.taxon));
+
+        opt.insert("quorum", flag(self.quorum));
+        opt.insert("merge", flag(self.merge));
+        opt.insert("prefilter", optional(&self.prefilter));
+        opt.insert("ecphase", self.ecphase.clone());
+
+        opt.insert("bwa", optional(&self.bwa));
+        opt.insert("caller", optional(&self.caller));
+        opt.insert("gatk", flag(self.gatk()));
+        opt.insert("freebayes", flag(self.freebayes()));
+
+        opt.insert("ont", optional(&self.ont));
+        opt.insert("pacbio", optional(&self.pacbio));
+        opt.insert(
+            "long",
+            optional(&self.ont.clone().or_else(|| self.pacbio.clone())),
+        );
+        opt.insert("longmin", self.longmin.to_string());
+        opt.insert("longidt", self.longidt.to_string());
+        opt.insert("canu", flag(self.canu && !self.meta));
+
+        opt.insert("cov", self.cov.clone());
+        opt.insert("unitigger", self.unitigger.clone());
+        opt.insert("splitp", self.splitp.to_string());
+        opt.insert("statp", self.statp.to_string());
+        opt.insert("readl", self.readl.to_string());
+        opt.insert("uscale", self.uscale.to_string());
+        opt.insert("lscale", self.lscale.to_string());
+        opt.insert("redo", flag(self.redo));
+        opt.insert("organelle", flag(self.organelle));
+
+        opt.insert("extend", flag(self.extend && !self.meta));
+        opt.insert("gluemin", self.gluemin.to_string());
+        opt.insert("fillmax", self.fillmax.to_string());
+
+        opt.insert("polish", flag(self.polish && !self.meta));
+        opt.insert("polisher", self.polisher.clone());
+        opt.insert("rounds", self.rounds.to_string());
+
+        // 9_quast.sh is always created
+        opt.insert("quast", flag(true));
+        opt.insert("busco", flag(self.busco));
+        opt.insert("merqury", flag(self.merqury));
+
+        opt
+    }
+
+    /// Stages of `--templates`
+    pub fn user_stages(&self) -> std::result::Result<Vec<UserStage>, std::io::Error> {
+        match &self.templates {
+            Some(dir) => read_user_stages(Path::new(dir)),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Scripts of the project, (file name, content)
+    pub fn render(&self) -> std::result::Result<Vec<(String, String)>, std::io::Error> {
+        Ok(self
+            .render_all(false)?
+            .into_iter()
+            .map(|r| (r.outname, r.content))
+            .collect())
+    }
+
+    /// Scripts with substitutions of `opt` between markers, see [`mark_opt`] and [`lint_script`]
+    pub fn render_marked(&self) -> std::result::Result<Vec<(String, String)>, std::io::Error> {
+        Ok(self
+            .render_all(true)?
+            .into_iter()
+            .map(|r| (r.outname, r.content))
+            .collect())
+    }
+
+    fn render_all(&self, marked: bool) -> std::result::Result<Vec<Rendered>, std::io::Error> {
+        self.check()?;
+
+        let opt = self.opt();
+
+        // tool => image
+        let images = match &self.images {
+            Some(images) => read_images(images),
+            None => BTreeMap::new(),
+        };
+
+        let unitiggers: Vec<&str> = self.unitigger.split_ascii_whitespace().collect();
+        let user_stages = self.user_stages()?;
+
+        // Built-in stages, replaced by user templates of the same names
+        let mut plan: Vec<Planned> = vec![];
+        for stage in STAGES.iter().filter(|s| (s.when)(self)) {
+            let content = match user_stages.iter().find(|u| u.name == stage.name) {
+                Some(u) => u.content.as_str(),
+                None => embedded(stage.template),
+            };
+            plan.extend(Planned::expand(
+                stage.name,
+                content,
+                stage.per_unitigger,
+                (stage.inputs, stage.outputs),
+                &unitiggers,
+            ));
+        }
+
+        // Other user stages
+        let mut added = vec![];
+        for stage in user_stages
+            .iter()
+            .filter(|u| !STAGES.iter().any(|s| s.name == u.name))
+        {
+            if let Some(after) = &stage.after {
+                let known = STAGES.iter().any(|s| s.name == after)
+                    || user_stages.iter().any(|u| &u.name == after);
+                if !known {
+                    return Err(invalid(&format!(
+                        "Unknown stage {} after {}",
+                        after, stage.name
+                    )));
+                }
+            }
+            if !stage.enabled(&opt) {
+                continue;
+            }
+
+            let pos = stage
+                .after
+                .as_ref()
+                .and_then(|after| plan.iter().rposition(|p| &p.name == after))
+                .map(|i| i + 1)
+                .or_else(|| plan.iter().position(|p| p.name == "0_cleanup"))
+                .unwrap_or(plan.len());
+            let expanded = Planned::expand(
+                &stage.name,
+                &stage.content,
+                stage.per_unitigger,
+                (&stage.inputs, &stage.outputs),
+                &unitiggers,
+            );
+            for (i, p) in expanded.into_iter().enumerate() {
+                added.push(p.outname.clone());
+                plan.insert(pos + i, p);
+            }
+        }
+
+        // Hooks of 0_master.sh and 0_bsub.sh run user stages after built-in ones,
+        // others run at last
+        let hooks = user_hooks();
+        let mut user_after: BTreeMap<String, Vec<String>> =
+            hooks.iter().map(|h| (h.to_string(), vec![])).collect();
+        let mut user_last = vec![];
+        for outname in &added {
+            let p = plan.iter().find(|p| &p.outname == outname).unwrap();
+            match user_anchor(&p.name, &user_stages, &plan) {
+                Some(anchor) if hooks.contains(&anchor) => {
+                    user_after.get_mut(anchor).unwrap().push(outname.clone())
+                }
+                _ => user_last.push(outname.clone()),
+            }
+        }
+
+        let mut context = Context::new();
+        context.insert("opt", &opt);
+        context.insert("images", &images);
+        context.insert("user_stages", &added);
+        context.insert("user_after", &user_after);
+        context.insert("user_last", &user_last);
+
+        // Each template is parsed once
+        let source = |content: &str| {
+            if marked {
+                mark_opt(content)
+            } else {
+                content.to_string()
+            }
+        };
+        let mut tera = Tera::default();
+        let mut raw = vec![
+            ("header", source(embedded("header"))),
+            ("user_hooks", source(embedded("user_hooks"))),
+        ];
+        for p in &plan {
+            if !raw.iter().any(|(name, _)| *name == p.name) {
+                raw.push((p.name.as_str(), source(p.content)));
+            }
+        }
+        tera.add_raw_templates(raw).map_err(tera_error)?;
+
+        let mut scripts = vec![];
+        for p in &plan {
+            let mut con = Context::new();
+            // 4_unitigs_bcalm.sh
+            if let Some(u) = p.unitigger {
+                con.insert("outname", &p.outname);
+                con.insert("unitigger", u);
+            }
+            con.extend(context.clone());
+
+            // With --genome auto, scripts using the genome size stop when it's missing
+            let render = |con: &Context| {
+                tera.render(&p.name, con)
+                    .map_err(|e| invalid(&format!("{}: {}", p.outname, tera_error(e))))
+            };
+            con.insert("genome_required", &false);
+            let mut rendered = render(&con)?;
+            if rendered.contains("${GENOME_SIZE}") {
+                con.insert("genome_required", &true);
+                rendered = render(&con)?;
+            }
+            scripts.push(Rendered {
+                outname: p.outname.clone(),
+                content: rendered + "\n",
+                inputs: p.inputs.clone(),
+                outputs: p.outputs.clone(),
+            });
+        }
+
+        Ok(scripts)
+    }
+
+    /// Contents of provenance.json
+    ///
+    /// `command` is the command line creating the project, null for library callers
+    pub fn provenance(&self, command: Option<&str>) -> String {
+        let mut hashes = BTreeMap::new();
+        for (name, content) in TEMPLATES {
+            hashes.insert(name.to_string(), format!("{:x}", md5::compute(content)));
+        }
+        for stage in self.user_stages().unwrap_or_default() {
+            hashes.insert(
+                format!("user/{}", stage.name),
+                format!("{:x}", md5::compute(&stage.content)),
+            );
+        }
+
+        // The option as given, not the shell variable in scripts
+        let mut options = self.opt();
+        options.insert("genome", self.genome.clone());
+
+        // stages and tools are appended by the generated scripts
+        let provenance = json!({
+            "anchr": env!("CARGO_PKG_VERSION"),
+            "command": command,
+            "options": options,
+            "templates": hashes,
+            "stages": [],
+            "tools": {},
+        });
+
+        serde_json::to_string_pretty(&provenance).unwrap() + "\n"
+    }
+
+    /// Scripts in run order with their files and tools. Nothing is written
+    ///
+    /// Inputs are looked up in outputs of earlier scripts, then in `dir`
+    pub fn plan(&self, dir: &Path) -> std::result::Result<Vec<PlanItem>, std::io::Error> {
+        let opt = self.opt();
+        let rendered = self.render_all(false)?;
+
+        // Run order of 0_master.sh, then scripts not in it
+        let mut order: Vec<&Rendered> = vec![];
+        let master = rendered.iter().find(|r| r.outname == "0_master.sh");
+        for line in master.map(|r| r.content.lines()).into_iter().flatten() {
+            let script = match line.trim().strip_prefix("retry bash ") {
+                Some(s) => s.split([' ', ';']).next().unwrap(),
+                None => continue,
+            };
+            if order.iter().any(|r| r.outname == script) {
+                continue;
+            }
+            if let Some(r) = rendered.iter().find(|r| r.outname == script) {
+                order.push(r);
+            }
+        }
+        for r in &rendered {
+            if !r.outname.starts_with("0_") && !order.iter().any(|o| o.outname == r.outname) {
+                order.push(r);
+            }
+        }
+
+        let mut items: Vec<PlanItem> = vec![];
+        for r in order {
+            let mut inputs = vec![];
+            for input in &r.inputs {
+                let (optional, input) = match input.strip_prefix('?') {
+                    Some(i) => (true, i),
+                    None => (false, input.as_str()),
+                };
+                let input = match input.split_once(':') {
+                    Some((cond, path)) => {
+                        if !condition(&opt, cond) {
+                            continue;
+                        }
+                        path
+                    }
+                    None => input,
+                };
+                let path = substitute(input, &opt);
+
+                let from = items
+                    .iter()
+                    .rev()
+                    .find(|i| i.outputs.iter().any(|o| produces(o, &path)));
+                let status = if let Some(from) = from {
+                    InputStatus::From(from.script.clone())
+                } else if glob_exists(dir, &path) {
+                    InputStatus::Present
+                } else if optional {
+                    InputStatus::Absent
+                } else {
+                    InputStatus::Missing
+                };
+                inputs.push((path, status));
+            }
+
+            let mut tools: Vec<String> = vec![];
+            for line in r.content.lines() {
+                let tool = match line.trim().strip_prefix("save_version ") {
+                    Some(t) => t.split_ascii_whitespace().next().unwrap_or("").to_string(),
+                    None => continue,
+                };
+                // `save_version () {` of the header
+                let is_name = tool.starts_with(|c: char| c.is_ascii_alphanumeric());
+                if is_name && !tools.contains(&tool) {
+                    tools.push(tool);
+                }
+            }
+
+            items.push(PlanItem {
+                script: r.outname.clone(),
+                inputs,
+                outputs: r.outputs.iter().map(|o| substitute(o, &opt)).collect(),
+                tools,
+            });
+        }
+
+        Ok(items)
+    }
+
+    /// Writes provenance.json and all scripts into `dir`, returns names of created files
+    ///
+    /// `command` is recorded in provenance.json, see [`TemplateOptions::provenance`]
+    pub fn write_project(
+        &self,
+        dir: &Path,
+        command: Option<&str>,
+    ) -> std::result::Result<Vec<String>, std::io::Error> {
+        let scripts = self.render()?;
+
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join("provenance.json"), self.provenance(command))?;
+
+        let mut names = vec!["provenance.json".to_string()];
+        for (outname, content) in scripts {
+            fs::write(dir.join(&outname), content)?;
+            names.push(outname);
+        }
+
+        Ok(names)
+    }
+}
+
+/// Scripts of `anchr batch` in the parent directory of projects, (file name, content)
+///
+/// 0_batch.sh runs 0_master.sh of each project, then 9_stat_batch.sh combines their stats
+pub fn render_batch(
+    projects: &[&str],
+) -> std::result::Result<Vec<(String, String)>, std::io::Error> {
+    let opt: BTreeMap<&str, String> = BTreeMap::new();
+    let images: BTreeMap<String, String> = BTreeMap::new();
+
+    let mut context = Context::new();
+    context.insert("opt", &opt);
+    context.insert("images", &images);
+    context.insert("samples", projects);
+
+    let mut tera = Tera::default();
+    tera.add_raw_templates(vec![
+        ("header", embedded("header")),
+        ("0_batch", embedded("0_batch")),
+        ("9_stat_batch", embedded("9_stat_batch")),
+    ])
+    .map_err(tera_error)?;
+
+    let mut scripts = vec![];
+    for name in ["0_batch", "9_stat_batch"] {
+        let rendered = tera.render(name, &context).map_err(tera_error)?;
+        scripts.push((format!("{}.sh", name), rendered + "\n"));
+    }
+
+    Ok(scripts)
+}
+
+// A script to be rendered
+struct Planned<'a> {
+    name: String,
+    outname: String,
+    content: &'a str,
+    unitigger: Option<&'a str>,
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+}
+
+impl<'a> Planned<'a> {
+    fn expand<S: AsRef<str>>(
+        name: &str,
+        content: &'a str,
+        per_unitigger: bool,
+        io: (&[S], &[S]),
+        unitiggers: &[&'a str],
+    ) -> Vec<Self> {
+        let planned = |outname: String, unitigger: Option<&'a str>| {
+            let paths = |paths: &[S]| -> Vec<String> {
+                paths
+                    .iter()
+                    .map(|p| p.as_ref().replace("{unitigger}", unitigger.unwrap_or("*")))
+                    .collect()
+            };
+            Planned {
+                name: name.to_string(),
+                outname,
+                content,
+                unitigger,
+                inputs: paths(io.0),
+                outputs: paths(io.1),
+            }
+        };
+
+        if per_unitigger {
+            unitiggers
+                .iter()
+                .map(|u| planned(format!("{}_{}.sh", name, u), Some(u)))
+                .collect()
+        } else {
+            vec![planned(format!("{}.sh", name), None)]
+        }
+    }
+}
+
+/// Status of an input of [`PlanItem`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputStatus {
+    /// Written by an earlier script
+    From(String),
+    Present,
+    /// An optional input that doesn't exist
+    Absent,
+    Missing,
+}
+
+/// A script of [`TemplateOptions::plan`]
+///
+/// Paths of inputs and outputs are relative to the project directory
+///
+/// * `?path`, an optional input
+/// * `key:path` or `!key:path`, only when `opt.key` is set or unset
+/// * `{key}` is replaced by `opt.key` and `{unitigger}` by the unitigger of the script
+/// * `*` matches any name, and a trailing `/` is a directory
+#[derive(Debug, Clone)]
+pub struct PlanItem {
+    pub script: String,
+    pub inputs: Vec<(String, InputStatus)>,
+    pub outputs: Vec<String>,
+    /// Tools recorded by `save_version`
+    pub tools: Vec<String>,
+}
+
+// Replaces {key} with values of opt
+fn substitute(path: &str, opt: &BTreeMap<&'static str, String>) -> String {
+    let mut path = path.to_string();
+    for (key, value) in opt {
+        path = path.replace(&format!("{{{}}}", key), value);
+    }
+    path
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    match pattern.find('*') {
+        None => pattern == text,
+        Some(i) => {
+            let (head, rest) = (&pattern[..i], &pattern[i + 1..]);
+            text.starts_with(head)
+                && (0..=text.len() - head.len()).any(|j| glob_match(rest, &text[head.len() + j..]))
+        }
+    }
+}
+
+// Whether the output is, contains or is inside the input. Both may contain `*`
+fn produces(output: &str, input: &str) -> bool {
+    let outs: Vec<&str> = output.trim_end_matches('/').split('/').collect();
+    let ins: Vec<&str> = input.trim_end_matches('/').split('/').collect();
+    if ins.len() < outs.len() && !input.ends_with('/') {
+        return false;
+    }
+    if ins.len() > outs.len() && !output.ends_with('/') {
+        return false;
+    }
+
+    outs.iter()
+        .zip(ins.iter())
+        .all(|(o, i)| glob_match(o, i) || glob_match(i, o))
+}
+
+fn glob_exists(dir: &Path, path: &str) -> bool {
+    let path = path.trim_end_matches('/');
+    if Path::new(path).is_absolute() {
+        return Path::new(path).exists();
+    }
+
+    let mut bases = vec![dir.to_path_buf()];
+    for seg in path.split('/') {
+        let mut next = vec![];
+        for base in &bases {
+            if seg.contains('*') {
+                for entry in fs::read_dir(base).into_iter().flatten().flatten() {
+                    if glob_match(seg, &entry.file_name().to_string_lossy()) {
+                        next.push(entry.path());
+                    }
+                }
+            } else if base.join(seg).exists() {
+                next.push(base.join(seg));
+            }
+        }
+        bases = next;
+    }
+
+    !bases.is_empty()
+}
+
+// A rendered script with its files
+struct Rendered {
+    outname: String,
+    content: String,
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+}
+
+// `key` is set in opt, or unset with a leading `!`
+fn condition(opt: &BTreeMap<&'static str, String>, cond: &str) -> bool {
+    let (negate, key) = match cond.strip_prefix('!') {
+        Some(k) => (true, k),
+        None => (false, cond),
+    };
+    let set = opt.get(key).is_some_and(|v| v != "0");
+    set != negate
+}
+
+// Built-in stages run by 0_master.sh and 0_bsub.sh, each followed by a hook of user stages
+fn user_hooks() -> Vec<&'static str> {
+    STAGES
+        .iter()
+        .filter(|s| !s.manual)
+        .map(|s| s.name)
+        .collect()
+}
+
+// The built-in stage that a user stage runs after, following `after` of other user stages.
+// None when it's not set or the stage is disabled
+fn user_anchor<'a>(name: &str, user_stages: &[UserStage], plan: &'a [Planned]) -> Option<&'a str> {
+    let mut name = name;
+    // at most one step per user stage, `after` may form a loop
+    for _ in 0..=user_stages.len() {
+        let after = user_stages
+            .iter()
+            .find(|u| u.name == name)?
+            .after
+            .as_deref()?;
+        if STAGES.iter().any(|s| s.name == after) {
+            return plan
+                .iter()
+                .find(|p| p.name == after)
+                .map(|p| p.name.as_str());
+        }
+        name = after;
+    }
+    None
+}
+
+fn embedded(name: &str) -> &'static str {
+    TEMPLATES
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, t)| *t)
+        .unwrap()
+}
+
+// Undefined variables are in the sources of the error
+fn tera_error(e: tera::Error) -> std::io::Error {
+    let mut msg = e.to_string();
+    let mut source = std::error::Error::source(&e);
+    while let Some(s) = source {
+        msg += &format!(": {}", s);
+        source = s.source();
+    }
+    std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+}
+
+// Around substitutions of opt.* in templates of `mark_opt()`
+const MARKS: [char; 2] = ['\u{2}', '\u{3}'];
+
+/// Puts substitutions like `{{ opt.sample }}` of a template between markers
+///
+/// Tags trimming whitespace, `{{- ... -}}`, are left as they are
+pub fn mark_opt(template: &str) -> String {
+    let mut marked = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let end = match rest[start..].find("}}") {
+            Some(i) => start + i + 2,
+            None => break,
+        };
+        let tag = &rest[start..end];
+        let expr = &tag[2..tag.len() - 2];
+
+        marked += &rest[..start];
+        if expr.trim_start().starts_with("opt.") && !expr.starts_with('-') && !expr.ends_with('-') {
+            marked.push(MARKS[0]);
+            marked += tag;
+            marked.push(MARKS[1]);
+        } else {
+            marked += tag;
+        }
+        rest = &rest[end..];
+    }
+    marked += rest;
+
+    marked
+}
+
+/// Problems of a rendered script
+///
+/// * Syntax errors reported by `bash -n`
+/// * Empty options like `in=` with nothing after it, when the script is rendered
+///   from templates of [`mark_opt`]. Shell like `local x=` is fine
+/// * Tera tags left in the script
+///
+/// ```
+/// use anchr::libs::template::{lint_script, mark_opt};
+///
+/// let mut context = tera::Context::new();
+/// context.insert("opt", &serde_json::json!({ "sample": "" }));
+/// let template = mark_opt("bbduk.sh in={{ opt.sample }} out=a.fq\nFOO= echo\n");
+/// let script = tera::Tera::one_off(&template, &context, false).unwrap();
+///
+/// let problems = lint_script(&script);
+/// assert_eq!(problems.len(), 1);
+/// assert!(problems[0].contains("in="));
+/// ```
+pub fn lint_script(content: &str) -> Vec<String> {
+    let mut problems = vec![];
+
+    let empty: String = MARKS.iter().collect();
+    for (i, line) in content.lines().enumerate() {
+        for word in line.split_ascii_whitespace() {
+            if let Some(key) = word.strip_suffix(&format!("={}", empty)) {
+                let key: String = key.chars().filter(|c| !MARKS.contains(c)).collect();
+                problems.push(format!("line {}: empty option {}=", i + 1, key));
+            }
+        }
+    }
+
+    let content: String = content.chars().filter(|c| !MARKS.contains(c)).collect();
+    for (i, line) in content.lines().enumerate() {
+        let leftover = line.contains("{{ ")
+            || line.contains(" }}")
+            || line.contains("{% ")
+            || line.contains("{%-")
+            || line.contains(" %}")
+            || line.contains("-%}");
+        if leftover {
+            problems.push(format!("line {}: Tera tags left", i + 1));
+        }
+    }
+
+    match bash_syntax(&content) {
+        Ok(None) => {}
+        Ok(Some(err)) => problems.extend(err.lines().map(|l| l.to_string())),
+        Err(e) => problems.push(format!("Can't run bash: {}", e)),
+    }
+
+    problems
+}
+
+// Errors of `bash -n`
+fn bash_syntax(content: &str) -> std::result::Result<Option<String>, std::io::Error> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new("bash")
+        .arg("-n")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    child.stdin.take().unwrap().write_all(content.as_bytes())?;
+    let output = child.wait_with_output()?;
+
+    if output.status.success() {
+        Ok(None)
+    } else {
+        Ok(Some(String::from_utf8_lossy(&output.stderr).to_string()))
+    }
+}
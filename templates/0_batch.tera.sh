@@ -0,0 +1,20 @@
+{%- include "header" -%}
+{# Keep a blank line #}
+#----------------------------#
+# Run
+#----------------------------#
+log_warn 0_batch.sh
+
+{% for s in samples -%}
+if [ -e {{ s }}/0_master.sh ]; then
+    log_info {{ s }}
+    bash {{ s }}/0_master.sh;
+fi
+
+{% endfor -%}
+#----------------------------#
+# combined report
+#----------------------------#
+if [ -e 9_stat_batch.sh ]; then
+    bash 9_stat_batch.sh;
+fi
@@ -1,3 +1,4 @@
+{% import "user_hooks" as user_hooks -%}
 {%- include "header" -%}
 {# Keep a blank line #}
 #----------------------------#
@@ -13,38 +14,65 @@ if [ -e 2_fastqc.sh ]; then
     bsub -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-2_fastqc" \
         "bash 2_fastqc.sh"
 fi
+{{- user_hooks::bsub(after=["2_fastqc"], wait="ended(${BASE_NAME}-2_fastqc)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 
 if [ -e 2_insert_size.sh ]; then
     bsub -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-2_insert_size" \
         "bash 2_insert_size.sh"
 fi
+{{- user_hooks::bsub(after=["2_insert_size"], wait="ended(${BASE_NAME}-2_insert_size)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 
 if [ -e 2_kat.sh ]; then
     bsub -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-2_kat" \
         "bash 2_kat.sh"
 fi
+{{- user_hooks::bsub(after=["2_kat"], wait="ended(${BASE_NAME}-2_kat)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 
 #----------------------------#
 # trim reads
 #----------------------------#
 bsub -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-2_trim" \
-    "bash 2_trim.sh"
+    "bash 2_trim.sh{% if opt.genome_auto == "1" %} && bash 2_kmer_profile.sh{% endif %}"
+{{- user_hooks::bsub(after=["2_trim", "2_kmer_profile"], wait="ended(${BASE_NAME}-2_trim)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 
-bsub -w "ended(${BASE_NAME}-2_trim)" \
+{% if opt.long != "0" -%}
+bsub -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-3_long" \
+    "bash 3_long.sh"
+{{- user_hooks::bsub(after=["3_long"], wait="ended(${BASE_NAME}-3_long)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
+
+{% endif -%}
+bsub -w "ended(${BASE_NAME}-2_trim){% if opt.long != "0" %} && ended(${BASE_NAME}-3_long){% endif %}" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-9_stat_reads" \
     "bash 9_stat_reads.sh"
+{{- user_hooks::bsub(after=["9_stat_reads"], wait="ended(${BASE_NAME}-9_stat_reads)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 
 if [ -e 3_bwa.sh ]; then
     bsub  -w "ended(${BASE_NAME}-2_trim)" \
         -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-3_bwa" \
         "bash 3_bwa.sh"
 fi
+{{- user_hooks::bsub(after=["3_bwa"], wait="ended(${BASE_NAME}-3_bwa)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 
 if [ -e 3_gatk.sh ]; then
     bsub  -w "ended(${BASE_NAME}-3_bwa)" \
         -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-3_gatk" \
         "bash 3_gatk.sh"
 fi
+{{- user_hooks::bsub(after=["3_gatk"], wait="ended(${BASE_NAME}-3_gatk)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
+
+if [ -e 3_freebayes.sh ]; then
+    bsub  -w "ended(${BASE_NAME}-3_bwa)" \
+        -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-3_freebayes" \
+        "bash 3_freebayes.sh"
+fi
+{{- user_hooks::bsub(after=["3_freebayes"], wait="ended(${BASE_NAME}-3_freebayes)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
+
+if [ -e 9_stat_variants.sh ]; then
+    bsub  -w "ended(${BASE_NAME}-3_bwa){% if opt.gatk == "1" %} && ended(${BASE_NAME}-3_gatk){% endif %}{% if opt.freebayes == "1" %} && ended(${BASE_NAME}-3_freebayes){% endif %}" \
+        -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-9_stat_variants" \
+        "bash 9_stat_variants.sh"
+fi
+{{- user_hooks::bsub(after=["9_stat_variants"], wait="ended(${BASE_NAME}-9_stat_variants)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 
 {% if opt.merge == "1" and opt.se == "0" -%}
 #----------------------------#
@@ -53,6 +81,7 @@ fi
 bsub -w "ended(${BASE_NAME}-2_trim)" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-2_merge" \
     "bash 2_merge.sh"
+{{- user_hooks::bsub(after=["2_merge"], wait="ended(${BASE_NAME}-2_merge)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 {% endif -%}
 {# Keep a blank line #}
 #----------------------------#
@@ -61,13 +90,15 @@ bsub -w "ended(${BASE_NAME}-2_trim)" \
 bsub -w "ended(${BASE_NAME}-2_trim)" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-2_quorum" \
     "bash 2_quorum.sh"
-
+{{- user_hooks::bsub(after=["2_quorum"], wait="ended(${BASE_NAME}-2_quorum)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
+{% if opt.meta != "1" %}
 #----------------------------#
 # down sampling trimmed reads; build unitigs and anchors
 #----------------------------#
 bsub -w "ended(${BASE_NAME}-2_quorum)" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-4_down_sampling" \
     "bash 4_down_sampling.sh"
+{{- user_hooks::bsub(after=["4_down_sampling"], wait="ended(${BASE_NAME}-4_down_sampling)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 
 {% for u in unitiggers -%}
 bsub -w "ended(${BASE_NAME}-4_down_sampling)" \
@@ -79,6 +110,11 @@ bsub -w "ended(${BASE_NAME}-4_down_sampling)" \
     "
 
 {% endfor -%}
+{% set_global waits = [] -%}
+{% for u in unitiggers -%}
+{% set_global waits = waits | concat(with="ended(${BASE_NAME}-4_unitigs_" ~ u ~ ")") -%}
+{% endfor -%}
+{{- user_hooks::bsub(after=["4_unitigs", "4_anchors", "9_stat_anchors"], wait=waits | join(sep=" && "), stages=user_after, queue=opt.queue, parallel=opt.parallel) -}}
 {# Keep a blank line #}
 {% if opt.merge == "1" and opt.se == "0" -%}
 #----------------------------#
@@ -87,6 +123,7 @@ bsub -w "ended(${BASE_NAME}-4_down_sampling)" \
 bsub -w "ended(${BASE_NAME}-2_merge)" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-6_down_sampling" \
     "bash 6_down_sampling.sh"
+{{- user_hooks::bsub(after=["6_down_sampling"], wait="ended(${BASE_NAME}-6_down_sampling)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 
 {% for u in unitiggers -%}
 bsub -w "ended(${BASE_NAME}-6_down_sampling)" \
@@ -98,6 +135,11 @@ bsub -w "ended(${BASE_NAME}-6_down_sampling)" \
     "
 
 {% endfor -%}
+{% set_global waits = [] -%}
+{% for u in unitiggers -%}
+{% set_global waits = waits | concat(with="ended(${BASE_NAME}-6_unitigs_" ~ u ~ ")") -%}
+{% endfor -%}
+{{- user_hooks::bsub(after=["6_unitigs", "6_anchors", "9_stat_mr_anchors"], wait=waits | join(sep=" && "), stages=user_after, queue=opt.queue, parallel=opt.parallel) -}}
 {% endif -%}
 {# Keep a blank line #}
 #----------------------------#
@@ -120,48 +162,88 @@ bsub -w "ended(${BASE_NAME}-6_unitigs_{{ u }})" \
 bsub -w "ended(${BASE_NAME}-2_quorum) {% for u in unitiggers %}&& ended(${BASE_NAME}-7_merge_anchors_4_unitigs_{{ u }}){% endfor %} {% if opt.merge == "1" and opt.se == "0" %}{% for u in unitiggers %}&& ended(${BASE_NAME}-7_merge_anchors_4_unitigs_{{ u }}){% endfor %}{% endif %}" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-7_merge_anchors" \
     "bash 7_merge_anchors.sh 7_merge 7_merge_anchors"
+{{- user_hooks::bsub(after=["7_merge_anchors"], wait="ended(${BASE_NAME}-7_merge_anchors)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 bsub -w "ended(${BASE_NAME}-7_merge_anchors)" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-9_stat_merge_anchors" \
     "bash 9_stat_merge_anchors.sh"
-
+{{- user_hooks::bsub(after=["9_stat_merge_anchors"], wait="ended(${BASE_NAME}-9_stat_merge_anchors)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
+{% endif -%}
+{# Keep a blank line #}
 #----------------------------#
-# spades, megahit and platanus
+# spades, megahit, platanus, flye and canu
 #----------------------------#
 bsub -w "ended(${BASE_NAME}-2_quorum)" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-8_spades" \
     "bash 8_spades.sh"
+{{- user_hooks::bsub(after=["8_spades"], wait="ended(${BASE_NAME}-8_spades)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 
 bsub -w "ended(${BASE_NAME}-2_quorum)" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-8_megahit" \
     "bash 8_megahit.sh"
+{{- user_hooks::bsub(after=["8_megahit"], wait="ended(${BASE_NAME}-8_megahit)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 
+{% if opt.meta != "1" -%}
 bsub -w "ended(${BASE_NAME}-2_quorum)" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-8_platanus" \
     "bash 8_platanus.sh"
+{{- user_hooks::bsub(after=["8_platanus"], wait="ended(${BASE_NAME}-8_platanus)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
+
+{% endif -%}
+{% if opt.long != "0" -%}
+bsub -w "ended(${BASE_NAME}-2_quorum) && ended(${BASE_NAME}-3_long)" \
+    -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-8_flye" \
+    "bash 8_flye.sh"
+{{- user_hooks::bsub(after=["8_flye"], wait="ended(${BASE_NAME}-8_flye)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
+{% if opt.canu == "1" -%}
+bsub -w "ended(${BASE_NAME}-2_quorum) && ended(${BASE_NAME}-3_long)" \
+    -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-8_canu" \
+    "bash 8_canu.sh"
+{{- user_hooks::bsub(after=["8_canu"], wait="ended(${BASE_NAME}-8_canu)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
+{% endif -%}
+{% endif -%}
 
 {% if opt.merge == "1" and opt.se == "0" -%}
 bsub -w "ended(${BASE_NAME}-2_merge)" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-8_mr_spades" \
     "bash 8_mr_spades.sh"
+{{- user_hooks::bsub(after=["8_mr_spades"], wait="ended(${BASE_NAME}-8_mr_spades)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 bsub -w "ended(${BASE_NAME}-2_merge)" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-8_mr_megahit" \
     "bash 8_mr_megahit.sh"
+{{- user_hooks::bsub(after=["8_mr_megahit"], wait="ended(${BASE_NAME}-8_mr_megahit)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 {% endif -%}
 {# Keep a blank line #}
-bsub -w "ended(${BASE_NAME}-8_spades) && ended(${BASE_NAME}-8_megahit) && ended(${BASE_NAME}-8_platanus) {% if opt.merge == "1" and opt.se == "0" %}&& ended(${BASE_NAME}-8_mr_spades) && ended(${BASE_NAME}-8_mr_megahit){% endif %}" \
+{% if opt.meta == "1" -%}
+bsub -w "ended(${BASE_NAME}-8_spades) && ended(${BASE_NAME}-8_megahit) {% if opt.merge == "1" and opt.se == "0" %}&& ended(${BASE_NAME}-8_mr_spades) && ended(${BASE_NAME}-8_mr_megahit){% endif %}{% if opt.long != "0" %} && ended(${BASE_NAME}-8_flye){% endif %}" \
+    -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-9_stat_meta" \
+    "bash 9_stat_meta.sh"
+{{- user_hooks::bsub(after=["9_stat_meta"], wait="ended(${BASE_NAME}-9_stat_meta)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
+{% else -%}
+bsub -w "ended(${BASE_NAME}-8_spades) && ended(${BASE_NAME}-8_megahit) && ended(${BASE_NAME}-8_platanus) {% if opt.merge == "1" and opt.se == "0" %}&& ended(${BASE_NAME}-8_mr_spades) && ended(${BASE_NAME}-8_mr_megahit){% endif %}{% if opt.long != "0" %} && ended(${BASE_NAME}-8_flye){% endif %}{% if opt.long != "0" and opt.canu == "1" %} && ended(${BASE_NAME}-8_canu){% endif %}" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-9_stat_other_anchors" \
     "bash 9_stat_other_anchors.sh"
-
+{{- user_hooks::bsub(after=["9_stat_other_anchors"], wait="ended(${BASE_NAME}-9_stat_other_anchors)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
+{% endif -%}
+{# Keep a blank line #}
 #----------------------------#
 # extend anchors
 #----------------------------#
-{% if opt.extend == "1" -%}
+{% if opt.long != "0" and opt.meta != "1" -%}
+bsub -w "ended(${BASE_NAME}-7_merge_anchors) && ended(${BASE_NAME}-3_long)" \
+    -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-7_glue_anchors" \
+    "bash 7_glue_anchors.sh 7_merge_anchors/anchor.merge.fasta 3_long/long.fasta 3 {{ opt.longidt }}"
+
+bsub -w "ended(${BASE_NAME}-7_glue_anchors)" \
+    -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-7_fill_anchors" \
+    "bash 7_fill_anchors.sh 7_glue_anchors/contig.fasta 3_long/long.fasta 3 {{ opt.longidt }}"
+{{- user_hooks::bsub(after=["7_glue_anchors", "7_fill_anchors"], wait="ended(${BASE_NAME}-7_fill_anchors)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
+{% elif opt.extend == "1" -%}
 bsub -w "ended(${BASE_NAME}-8_spades) && ended(${BASE_NAME}-8_megahit) && ended(${BASE_NAME}-8_platanus) {% if opt.merge == "1" and opt.se == "0" %}&& ended(${BASE_NAME}-8_mr_spades)&& ended(${BASE_NAME}-8_mr_megahit){% endif %}" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-contigs_2GS" \
     '
     rm -fr 7_extend_anchors
     mkdir -p 7_extend_anchors
-    cat \
+    for FILE in \
         8_spades/spades.non-contained.fasta \
         8_megahit/megahit.non-contained.fasta \
         8_platanus/platanus.non-contained.fasta \
@@ -169,6 +251,11 @@ bsub -w "ended(${BASE_NAME}-8_spades) && ended(${BASE_NAME}-8_megahit) && ended(
         8_mr_spades/spades.non-contained.fasta \
         8_mr_megahit/megahit.non-contained.fasta \
 {% endif -%}
+        ; do
+        if [ -e ${FILE} ]; then
+            cat ${FILE}
+        fi
+    done \
         | faops dazz -a -l 0 stdin stdout \
         | faops filter -a 1000 -l 0 stdin 7_extend_anchors/contigs.2GS.fasta
     '
@@ -180,19 +267,50 @@ bsub -w "ended(${BASE_NAME}-7_merge_anchors) && ended(${BASE_NAME}-contigs_2GS)"
 bsub -w "ended(${BASE_NAME}-7_glue_anchors)" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-7_fill_anchors" \
     "bash 7_fill_anchors.sh 7_glue_anchors/contig.fasta 7_extend_anchors/contigs.2GS.fasta 3"
+{{- user_hooks::bsub(after=["7_glue_anchors", "7_fill_anchors"], wait="ended(${BASE_NAME}-7_fill_anchors)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 {% endif -%}
 {# Keep a blank line #}
+{% if opt.polish == "1" -%}
+#----------------------------#
+# polish
+#----------------------------#
+bsub -w "ended(${BASE_NAME}-7_merge_anchors){% if opt.extend == "1" or opt.long != "0" %} && ended(${BASE_NAME}-7_fill_anchors){% endif %}{% if opt.long != "0" %} && ended(${BASE_NAME}-3_long){% endif %}" \
+    -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-7_polish" \
+    "bash 7_polish.sh"
+{{- user_hooks::bsub(after=["7_polish"], wait="ended(${BASE_NAME}-7_polish)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
+
+{% endif -%}
 #----------------------------#
 # final stats
 #----------------------------#
-bsub -w "ended(${BASE_NAME}-7_merge_anchors) && ended(${BASE_NAME}-8_spades) && ended(${BASE_NAME}-8_platanus) {% if opt.extend == "1" %}&& ended(${BASE_NAME}-7_fill_anchors){% endif %}" \
+{% if opt.merqury == "1" -%}
+bsub -w "{% if opt.meta == "1" %}ended(${BASE_NAME}-8_spades) && ended(${BASE_NAME}-8_megahit) {% else %}ended(${BASE_NAME}-7_merge_anchors) && ended(${BASE_NAME}-8_spades) && ended(${BASE_NAME}-8_platanus) {% if opt.extend == "1" or opt.long != "0" %}&& ended(${BASE_NAME}-7_fill_anchors){% endif %}{% endif %}{% if opt.long != "0" %} && ended(${BASE_NAME}-8_flye){% endif %}{% if opt.long != "0" and opt.canu == "1" %} && ended(${BASE_NAME}-8_canu){% endif %}{% if opt.polish == "1" %} && ended(${BASE_NAME}-7_polish){% endif %}" \
+    -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-9_merqury" \
+    "bash 9_merqury.sh"
+{{- user_hooks::bsub(after=["9_merqury"], wait="ended(${BASE_NAME}-9_merqury)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
+
+{% endif -%}
+bsub -w "{% if opt.meta == "1" %}ended(${BASE_NAME}-8_spades) && ended(${BASE_NAME}-8_megahit) {% else %}ended(${BASE_NAME}-7_merge_anchors) && ended(${BASE_NAME}-8_spades) && ended(${BASE_NAME}-8_platanus) {% if opt.extend == "1" or opt.long != "0" %}&& ended(${BASE_NAME}-7_fill_anchors){% endif %}{% endif %}{% if opt.long != "0" %} && ended(${BASE_NAME}-8_flye){% endif %}{% if opt.long != "0" and opt.canu == "1" %} && ended(${BASE_NAME}-8_canu){% endif %}{% if opt.polish == "1" %} && ended(${BASE_NAME}-7_polish){% endif %}{% if opt.merqury == "1" %} && ended(${BASE_NAME}-9_merqury){% endif %}" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-9_stat_final" \
     "bash 9_stat_final.sh"
+{{- user_hooks::bsub(after=["9_stat_final"], wait="ended(${BASE_NAME}-9_stat_final)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 
-bsub -w "ended(${BASE_NAME}-7_merge_anchors) && ended(${BASE_NAME}-8_spades) && ended(${BASE_NAME}-8_platanus) {% if opt.extend == "1" %}&& ended(${BASE_NAME}-7_fill_anchors){% endif %}" \
+bsub -w "{% if opt.meta == "1" %}ended(${BASE_NAME}-8_spades) && ended(${BASE_NAME}-8_megahit) {% else %}ended(${BASE_NAME}-7_merge_anchors) && ended(${BASE_NAME}-8_spades) && ended(${BASE_NAME}-8_platanus) {% if opt.extend == "1" or opt.long != "0" %}&& ended(${BASE_NAME}-7_fill_anchors){% endif %}{% endif %}{% if opt.long != "0" %} && ended(${BASE_NAME}-8_flye){% endif %}{% if opt.long != "0" and opt.canu == "1" %} && ended(${BASE_NAME}-8_canu){% endif %}{% if opt.polish == "1" %} && ended(${BASE_NAME}-7_polish){% endif %}" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-9_quast" \
     "bash 9_quast.sh"
+{{- user_hooks::bsub(after=["9_quast"], wait="ended(${BASE_NAME}-9_quast)", stages=user_after, queue=opt.queue, parallel=opt.parallel) }}
 
+{% if user_last | length > 0 -%}
+#----------------------------#
+# user stages
+#----------------------------#
+{% for s in user_last -%}
 bsub -w "ended(${BASE_NAME}-9_stat_final) && ended(${BASE_NAME}-9_quast)" \
+    -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-{{ s | trim_end_matches(pat=".sh") }}" \
+    "bash {{ s }}"
+
+{% endfor -%}
+{% endif -%}
+bsub -w "ended(${BASE_NAME}-9_stat_final) && ended(${BASE_NAME}-9_quast){% for s in user_stages %} && ended(${BASE_NAME}-{{ s | trim_end_matches(pat=".sh") }}){% endfor %}" \
     -q {{ opt.queue }} -n {{ opt.parallel }} -J "${BASE_NAME}-0_cleanup" \
     "bash 0_cleanup.sh"
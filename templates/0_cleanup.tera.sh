@@ -12,7 +12,7 @@ parallel --no-run-if-empty --linebuffer -k -j 1 "
         rm 2_illumina/{}.fq.gz;
         touch 2_illumina/{}.fq.gz;
     fi
-    " ::: clumpify filteredbytile sample trim filter
+    " ::: clumpify filteredbytile sample trim filter screen
 
 # insertSize
 rm -f 2_illumina/insert_size/*tadpole.contig.fa*
@@ -60,6 +60,9 @@ find . -type f -path "*8_platanus/*" -name "[ps]e.fa" | parallel --no-run-if-emp
 find . -type d -name "nucmer_output" | parallel --no-run-if-empty -j 1 rm -fr
 find . -type f -path "*contigs_reports/*" -name "*.stdout*" -or -name "*.stderr*" | parallel --no-run-if-empty -j 1 rm
 
+# merqury
+find . -type d -path "*9_merqury/*" -name "*.meryl" | parallel --no-run-if-empty -j 1 rm -fr
+
 # LSF outputs and dumps
 find . -type f -name "output.*" | parallel --no-run-if-empty -j 1 rm
 find . -type f -name "core.*"   | parallel --no-run-if-empty -j 1 rm
@@ -80,6 +83,11 @@ if [ -e statSgaStats.md ]; then
     cat statSgaStats.md;
     echo;
 fi
+if [ -e statScreen.md ]; then
+    echo;
+    cat statScreen.md;
+    echo;
+fi
 if [ -e statReads.md ]; then
     echo;
     cat statReads.md;
@@ -95,11 +103,21 @@ if [ -e statMergeReads.md ]; then
     cat statMergeReads.md;
     echo;
 fi
+if [ -e statKmerProfile.md ]; then
+    echo;
+    cat statKmerProfile.md;
+    echo;
+fi
 if [ -e statQuorum.md ]; then
     echo;
     cat statQuorum.md;
     echo;
 fi
+if [ -e statVariants.md ]; then
+    echo;
+    cat statVariants.md;
+    echo;
+fi
 if [ -e statAnchors.md ]; then
     echo;
     cat statAnchors.md;
@@ -122,13 +140,28 @@ if [ -e statMergeAnchors.md ]; then
     cat statMergeAnchors.md;
     echo;
 fi
+if [ -e statMeta.md ]; then
+    echo;
+    cat statMeta.md;
+    echo;
+fi
 if [ -e statOtherAnchors.md ]; then
     echo;
     cat statOtherAnchors.md;
     echo;
 fi
+if [ -e statPolish.md ]; then
+    echo;
+    cat statPolish.md;
+    echo;
+fi
 if [ -e statFinal.md ]; then
     echo;
     cat statFinal.md;
     echo;
 fi
+if [ -e statMerqury.md ]; then
+    echo;
+    cat statMerqury.md;
+    echo;
+fi
@@ -1,3 +1,4 @@
+{% import "user_hooks" as user_hooks -%}
 {%- include "header" -%}
 {# Keep a blank line #}
 #----------------------------#
@@ -10,90 +11,120 @@ log_warn 0_master.sh
 # Illumina QC
 #----------------------------#
 if [ -e 2_fastqc.sh ]; then
-    bash 2_fastqc.sh;
+    retry bash 2_fastqc.sh;
 fi
+{{- user_hooks::master(after=["2_fastqc"], stages=user_after) }}
 
 if [ -e 2_insert_size.sh ]; then
-    bash 2_insert_size.sh;
+    retry bash 2_insert_size.sh;
 fi
+{{- user_hooks::master(after=["2_insert_size"], stages=user_after) }}
 
 if [ -e 2_kat.sh ]; then
-    bash 2_kat.sh;
+    retry bash 2_kat.sh;
 fi
+{{- user_hooks::master(after=["2_kat"], stages=user_after) }}
 
 #----------------------------#
 # trim reads
 #----------------------------#
 if [ -e 2_trim.sh ]; then
-    bash 2_trim.sh;
+    retry bash 2_trim.sh;
 fi
+{{- user_hooks::master(after=["2_trim"], stages=user_after) }}
+if [ -e 2_kmer_profile.sh ]; then
+    retry bash 2_kmer_profile.sh;
+fi
+{{- user_hooks::master(after=["2_kmer_profile"], stages=user_after) }}
+
+if [ -e 3_long.sh ]; then
+    retry bash 3_long.sh;
+fi
+{{- user_hooks::master(after=["3_long"], stages=user_after) }}
 
 if [ -e 9_stat_reads.sh ]; then
-    bash 9_stat_reads.sh;
+    retry bash 9_stat_reads.sh;
 fi
+{{- user_hooks::master(after=["9_stat_reads"], stages=user_after) }}
 
 #----------------------------#
 # merge reads
 #----------------------------#
 if [ -e 2_merge.sh ]; then
-    bash 2_merge.sh;
+    retry bash 2_merge.sh;
 fi
+{{- user_hooks::master(after=["2_merge"], stages=user_after) }}
 
 #----------------------------#
 # quorum
 #----------------------------#
 if [ -e 2_quorum.sh ]; then
-    bash 2_quorum.sh;
+    retry bash 2_quorum.sh;
 fi
+{{- user_hooks::master(after=["2_quorum"], stages=user_after) }}
 
 #----------------------------#
 # mapping
 #----------------------------#
 if [ -e 3_bwa.sh ]; then
-    bash 3_bwa.sh;
+    retry bash 3_bwa.sh;
 fi
+{{- user_hooks::master(after=["3_bwa"], stages=user_after) }}
 if [ -e 3_gatk.sh ]; then
-    bash 3_gatk.sh;
+    retry bash 3_gatk.sh;
 fi
+{{- user_hooks::master(after=["3_gatk"], stages=user_after) }}
+if [ -e 3_freebayes.sh ]; then
+    retry bash 3_freebayes.sh;
+fi
+{{- user_hooks::master(after=["3_freebayes"], stages=user_after) }}
+if [ -e 9_stat_variants.sh ]; then
+    retry bash 9_stat_variants.sh;
+fi
+{{- user_hooks::master(after=["9_stat_variants"], stages=user_after) }}
 
 #----------------------------#
 # down sampling trimmed reads; build unitigs and anchors
 #----------------------------#
 if [ -e 4_down_sampling.sh ]; then
-    bash 4_down_sampling.sh;
+    retry bash 4_down_sampling.sh;
 fi
+{{- user_hooks::master(after=["4_down_sampling"], stages=user_after) }}
 
 {% for u in unitiggers -%}
 if [ -e 4_unitigs_{{ u }}.sh ]; then
-    bash 4_unitigs_{{ u }}.sh;
+    retry bash 4_unitigs_{{ u }}.sh;
 fi
 if [ -e 4_anchors.sh ]; then
-    bash 4_anchors.sh 4_unitigs_{{ u }};
+    retry bash 4_anchors.sh 4_unitigs_{{ u }};
 fi
 if [ -e 9_stat_anchors.sh ]; then
-    bash 9_stat_anchors.sh 4_unitigs_{{ u }} statUnitigs{{ u | title }}.md
+    retry bash 9_stat_anchors.sh 4_unitigs_{{ u }} statUnitigs{{ u | title }}.md
 fi
 {% endfor -%}
+{{- user_hooks::master(after=["4_unitigs", "4_anchors", "9_stat_anchors"], stages=user_after) -}}
 {# Keep a blank line #}
 {% if opt.merge == "1" and opt.se == "0" -%}
 #----------------------------#
 # down sampling merged reads
 #----------------------------#
 if [ -e 6_down_sampling.sh ]; then
-    bash 6_down_sampling.sh
+    retry bash 6_down_sampling.sh
 fi
+{{- user_hooks::master(after=["6_down_sampling"], stages=user_after) }}
 
 {% for u in unitiggers -%}
 if [ -e 6_unitigs_{{ u }}.sh ]; then
-    bash 6_unitigs_{{ u }}.sh;
+    retry bash 6_unitigs_{{ u }}.sh;
 fi
 if [ -e 6_anchors.sh ]; then
-    bash 6_anchors.sh 6_unitigs_{{ u }};
+    retry bash 6_anchors.sh 6_unitigs_{{ u }};
 fi
 if [ -e 9_stat_anchors.sh ]; then
-    bash 9_stat_mr_anchors.sh 6_unitigs_{{ u }} statMRUnitigs{{ u | title }}.md
+    retry bash 9_stat_mr_anchors.sh 6_unitigs_{{ u }} statMRUnitigs{{ u | title }}.md
 fi
 {% endfor -%}
+{{- user_hooks::master(after=["6_unitigs", "6_anchors", "9_stat_mr_anchors"], stages=user_after) -}}
 {% endif -%}
 {# Keep a blank line #}
 #----------------------------#
@@ -101,56 +132,84 @@ fi
 #----------------------------#
 {% for u in unitiggers -%}
 if [ -e 7_merge_anchors.sh ]; then
-    bash 7_merge_anchors.sh 4_unitigs_{{ u }} 7_merge_unitigs_{{ u }};
+    retry bash 7_merge_anchors.sh 4_unitigs_{{ u }} 7_merge_unitigs_{{ u }};
 fi
 {% endfor -%}
 {# Keep a blank line #}
 {% if opt.merge == "1" and opt.se == "0" -%}
 {% for u in unitiggers -%}
 if [ -e 7_merge_anchors.sh ]; then
-    bash 7_merge_anchors.sh 6_unitigs_{{ u }} 7_merge_mr_unitigs_{{ u }}
+    retry bash 7_merge_anchors.sh 6_unitigs_{{ u }} 7_merge_mr_unitigs_{{ u }}
 fi
 {% endfor -%}
 {% endif -%}
 {# Keep a blank line #}
 if [ -e 7_merge_anchors.sh ]; then
-    bash 7_merge_anchors.sh 7_merge 7_merge_anchors;
+    retry bash 7_merge_anchors.sh 7_merge 7_merge_anchors;
 fi
+{{- user_hooks::master(after=["7_merge_anchors"], stages=user_after) }}
 
 if [ -e 9_stat_merge_anchors.sh ]; then
-    bash 9_stat_merge_anchors.sh
+    retry bash 9_stat_merge_anchors.sh
 fi
+{{- user_hooks::master(after=["9_stat_merge_anchors"], stages=user_after) }}
 
 #----------------------------#
-# spades, megahit and platanus
+# spades, megahit, platanus, flye and canu
 #----------------------------#
 if [ -e 8_spades.sh ]; then
-    bash 8_spades.sh;
+    retry bash 8_spades.sh;
 fi
+{{- user_hooks::master(after=["8_spades"], stages=user_after) }}
 if [ -e 8_mr_spades.sh ]; then
-    bash 8_mr_spades.sh;
+    retry bash 8_mr_spades.sh;
 fi
+{{- user_hooks::master(after=["8_mr_spades"], stages=user_after) }}
 if [ -e 8_megahit.sh ]; then
-    bash 8_megahit.sh;
+    retry bash 8_megahit.sh;
 fi
+{{- user_hooks::master(after=["8_megahit"], stages=user_after) }}
 if [ -e 8_mr_megahit.sh ]; then
-    bash 8_mr_megahit.sh;
+    retry bash 8_mr_megahit.sh;
 fi
+{{- user_hooks::master(after=["8_mr_megahit"], stages=user_after) }}
 if [ -e 8_platanus.sh ]; then
-    bash 8_platanus.sh;
+    retry bash 8_platanus.sh;
+fi
+{{- user_hooks::master(after=["8_platanus"], stages=user_after) }}
+if [ -e 8_flye.sh ]; then
+    retry bash 8_flye.sh;
 fi
+{{- user_hooks::master(after=["8_flye"], stages=user_after) }}
+if [ -e 8_canu.sh ]; then
+    retry bash 8_canu.sh;
+fi
+{{- user_hooks::master(after=["8_canu"], stages=user_after) }}
 
 if [ -e 9_stat_other_anchors.sh ]; then
-    bash 9_stat_other_anchors.sh;
+    retry bash 9_stat_other_anchors.sh;
+fi
+{{- user_hooks::master(after=["9_stat_other_anchors"], stages=user_after) }}
+if [ -e 9_stat_meta.sh ]; then
+    retry bash 9_stat_meta.sh;
 fi
+{{- user_hooks::master(after=["9_stat_meta"], stages=user_after) }}
 
 #----------------------------#
 # extend anchors
 #----------------------------#
-{% if opt.extend == "1" -%}
+{% if opt.long != "0" -%}
+if [ -e 7_glue_anchors.sh ]; then
+    retry bash 7_glue_anchors.sh 7_merge_anchors/anchor.merge.fasta 3_long/long.fasta 3 {{ opt.longidt }};
+fi
+if [ -e 7_fill_anchors.sh ]; then
+    retry bash 7_fill_anchors.sh 7_glue_anchors/contig.fasta 3_long/long.fasta 3 {{ opt.longidt }};
+fi
+{% elif opt.extend == "1" -%}
 rm -fr 7_extend_anchors
 mkdir -p 7_extend_anchors
-cat \
+# assemblers may fail and leave no contigs
+for FILE in \
     8_spades/spades.non-contained.fasta \
     8_megahit/megahit.non-contained.fasta \
     8_platanus/platanus.non-contained.fasta \
@@ -158,23 +217,56 @@ cat \
     8_mr_spades/spades.non-contained.fasta \
     8_mr_megahit/megahit.non-contained.fasta \
 {% endif -%}
+    ; do
+    if [ -e ${FILE} ]; then
+        cat ${FILE}
+    fi
+done \
     | faops dazz -a -l 0 stdin stdout \
     | faops filter -a 1000 -l 0 stdin 7_extend_anchors/contigs.2GS.fasta
 
 if [ -e 7_glue_anchors.sh ]; then
-    bash 7_glue_anchors.sh 7_merge_anchors/anchor.merge.fasta 7_extend_anchors/contigs.2GS.fasta 3;
+    retry bash 7_glue_anchors.sh 7_merge_anchors/anchor.merge.fasta 7_extend_anchors/contigs.2GS.fasta 3;
 fi
 if [ -e 7_fill_anchors.sh ]; then
-    bash 7_fill_anchors.sh 7_glue_anchors/contig.fasta 7_extend_anchors/contigs.2GS.fasta 3;
+    retry bash 7_fill_anchors.sh 7_glue_anchors/contig.fasta 7_extend_anchors/contigs.2GS.fasta 3;
 fi
 {% endif -%}
+{{- user_hooks::master(after=["7_glue_anchors", "7_fill_anchors"], stages=user_after) -}}
 {# Keep a blank line #}
+{% if opt.polish == "1" -%}
+#----------------------------#
+# polish
+#----------------------------#
+if [ -e 7_polish.sh ]; then
+    retry bash 7_polish.sh;
+fi
+{{- user_hooks::master(after=["7_polish"], stages=user_after) }}
+
+{% endif -%}
 #----------------------------#
 # final stats
 #----------------------------#
+if [ -e 9_merqury.sh ]; then
+    retry bash 9_merqury.sh;
+fi
+{{- user_hooks::master(after=["9_merqury"], stages=user_after) }}
 if [ -e 9_stat_final.sh ]; then
-    bash 9_stat_final.sh;
+    retry bash 9_stat_final.sh;
 fi
+{{- user_hooks::master(after=["9_stat_final"], stages=user_after) }}
 if [ -e 9_quast.sh ]; then
-    bash 9_quast.sh;
+    retry bash 9_quast.sh;
 fi
+{{- user_hooks::master(after=["9_quast"], stages=user_after) }}
+{% if user_last | length > 0 -%}
+{# Keep a blank line #}
+#----------------------------#
+# user stages
+#----------------------------#
+{% for s in user_last -%}
+if [ -e {{ s }} ]; then
+    retry bash {{ s }};
+fi
+{% endfor -%}
+{% endif -%}
@@ -32,17 +32,24 @@ rm -fr 7_anchor*
 rm -fr 7_extend_anchors
 rm -fr 7_glue_anchors
 rm -fr 7_fill_anchors
+rm -fr 7_polish
 
-# spades, platanus, and megahit
+# spades, platanus, megahit, flye and canu
 rm -fr 8_spades*
 rm -fr 8_megahit*
 rm -fr 8_platanus*
+rm -fr 8_flye*
+rm -fr 8_canu*
 rm -fr 8_mr_spades*
 rm -fr 8_mr_megahit*
 
 # quast
 rm -fr 9_quast*
 
+# merqury
+rm -fr 9_merqury
+rm -fr 9_meta
+
 # tempdir
 find . -type d -name "\?" | parallel --no-run-if-empty -j 1 rm -fr
 
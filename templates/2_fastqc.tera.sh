@@ -5,6 +5,8 @@
 #----------------------------#
 log_warn 2_fastqc.sh
 
+save_version fastqc
+
 mkdir -p 2_illumina/fastqc
 cd 2_illumina/fastqc
 
@@ -5,6 +5,9 @@
 #----------------------------#
 log_warn 2_insert_size.sh
 
+save_version bbtools bbversion.sh
+save_version picard "picard SortSam --version"
+
 mkdir -p 2_illumina/insert_size
 cd 2_illumina/insert_size
 
@@ -5,6 +5,8 @@
 #----------------------------#
 log_warn 2_kat.sh
 
+save_version kat
+
 mkdir -p 2_illumina/kat
 cd 2_illumina/kat
 
@@ -34,24 +36,33 @@ done
 
 find . -type f -name "*.mx" | parallel --no-run-if-empty -j 1 rm
 
-echo -e "Table: statKAT\n" > statKAT.md
+# raw numbers, genome sizes in bp and GC contents in percent
+printf "%s\t%s\t%s\t%s\t%s\n" \
+    "k" "mean_freq" "est_genome_size_bp" "est_het_rate" "mean_gc_pct" \
+    > statKAT.tsv
 
 for PREFIX in R S T; do
     find . -type f -name "${PREFIX}-gcp*.dist_analysis.json" |
         sort |
         xargs cat |
         sed 's/%//g' |
-        jq "{
-            k: (\"${PREFIX}.\" + (.coverage.k | tostring)),
-            mean_freq: .coverage.mean_freq,
-            est_genome_size: .coverage.est_genome_size,
-            est_het_rate: .coverage.est_het_rate,
-            mean_gc: .gc.mean_gc,
-        }"
-done |
-    mlr --ijson --otsv cat |
+        jq -r "[
+            \"${PREFIX}.\" + (.coverage.k | tostring),
+            .coverage.mean_freq,
+            .coverage.est_genome_size,
+            .coverage.est_het_rate,
+            .gc.mean_gc
+        ] | @tsv"
+done \
+    >> statKAT.tsv
+
+echo -e "Table: statKAT\n" > statKAT.md
+cat statKAT.tsv |
     perl -nla -F"\t" -e '
-        /mean_gc/ and print and next;
+        if ( $. == 1 ) {
+            print join qq(\t), qw(k mean_freq est_genome_size est_het_rate mean_gc);
+            next;
+        }
         $F[3] = sprintf q(%.4f), $F[3];
         $F[4] = sprintf q(%.2f), $F[4];
         print join qq(\t), @F;
@@ -60,6 +71,7 @@ done |
     >> statKAT.md
 
 cat statKAT.md
-mv statKAT.md ../../
+anchr report --table statKAT.tsv
+mv statKAT.md statKAT.tsv statKAT.json ../../
 
 exit 0
@@ -0,0 +1,45 @@
+{%- include "header" -%}
+{# Keep a blank line #}
+#----------------------------#
+# Run
+#----------------------------#
+log_warn 2_kmer_profile.sh
+
+save_version anchr
+
+mkdir -p 2_illumina/kmer
+cd 2_illumina/kmer
+
+if [ -e env.json ]; then
+    log_debug "2_illumina/kmer/env.json presents"
+    exit;
+fi
+
+# Histograms of kmercountexact.sh in 2_trim.sh
+for PREFIX in R S T; do
+    if [ -e ../trim/${PREFIX}.khist.txt ]; then
+        break;
+    fi
+done
+
+if [ ! -e ../trim/${PREFIX}.khist.txt ]; then
+    log_info "2_illumina/trim/${PREFIX}.khist.txt does not exist"
+    exit;
+fi
+
+anchr kmer-profile \
+    ../trim/${PREFIX}.khist.txt \
+    -k {{ opt.cutk }} \
+    -o env.json
+
+echo -e "Table: statKmerProfile\n" > statKmerProfile.md
+printf "| %s | %s |\n" "Item" "Value" >> statKmerProfile.md
+printf "|:--|--:|\n" >> statKmerProfile.md
+jq -r 'to_entries[] | "| \(.key) | \(.value) |"' env.json >> statKmerProfile.md
+
+cat statKmerProfile.md
+mv statKmerProfile.md ../../
+
+log_info Done.
+
+exit 0
@@ -5,6 +5,8 @@
 #----------------------------#
 log_warn 2_merge.sh
 
+save_version bbtools bbversion.sh
+
 if [ -e 2_illumina/merge/pe.cor.fa.gz ]; then
     log_debug "2_illumina/merge/pe.cor.fa.gz presents"
     exit;
@@ -50,18 +52,18 @@ for PREFIX in R S T; do
     bash merge.sh
 
     # Create .cor.fa.gz
-    faops interleave \
+    anchr reads interleave --fasta \
         -p unmerged \
         ${PREFIXU}1.fq.gz \
         ${PREFIXU}2.fq.gz \
         > ${PREFIXM}.interleave.fa
 
-    faops interleave \
+    anchr reads interleave --fasta \
         -p single \
         ${PREFIXU}s.fq.gz \
         >> ${PREFIXM}.interleave.fa
 
-    faops interleave \
+    anchr reads interleave --fasta \
         -p merged \
         ${PREFIXM}1.fq.gz \
         >> ${PREFIXM}.interleave.fa
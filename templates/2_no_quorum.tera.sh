@@ -31,10 +31,10 @@ for Q in 0 {{ opt.qual }}; do
             fi
 
             log_info "Qual-Len: Q${Q}L${L}.${PREFIX}"
-            log_info "    faops interleave"
+            log_info "    anchr reads interleave"
 
             # Create .cor.fa.gz
-            faops interleave \
+            anchr reads interleave --fasta \
                 -p pe \
                 ${PREFIX}1.fq.gz \
 {% if opt.se == "0" -%}
@@ -43,7 +43,7 @@ for Q in 0 {{ opt.qual }}; do
                 > ${PREFIX}.interleave.fa
 
             if [ -e ${PREFIX}s.fq.gz ]; then
-                faops interleave \
+                anchr reads interleave --fasta \
                     -p se \
                     ${PREFIX}s.fq.gz \
                     >> ${PREFIX}.interleave.fa
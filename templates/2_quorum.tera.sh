@@ -5,6 +5,9 @@
 #----------------------------#
 log_warn 2_quorum.sh
 
+save_version quorum
+save_version jellyfish
+
 for Q in 0 {{ opt.qual }}; do
     for L in 0 {{ opt.len }}; do
         cd ${BASH_DIR}
@@ -5,6 +5,12 @@
 #----------------------------#
 log_warn 2_trim.sh
 
+save_version anchr
+save_version bbtools bbversion.sh
+{% if opt.screen != "0" -%}
+save_version {{ opt.screener }}
+{% endif -%}
+
 mkdir -p 2_illumina/trim
 cd 2_illumina/trim
 
@@ -22,6 +28,13 @@ for PREFIX in R S T; do
         {{ opt.trim }} \
         --qual "{{ opt.qual }}" \
         --len "{{ opt.len }}" \
+{%- if opt.screen != "0" %}
+        --screen "{{ opt.screen }}" \
+        --screener {{ opt.screener }} \
+{%- if opt.taxon != "0" %}
+        --taxon "{{ opt.taxon }}" \
+{%- endif %}
+{%- endif %}
     {% if opt.filter -%}
         --filter "{{ opt.filter }}" \
     {% endif -%}
@@ -46,7 +59,7 @@ for PREFIX in R S T; do
         printf "|:--|--:|--:|--:|\n" >> statTrimReads.md
     fi
 
-    for NAME in clumpify filteredbytile highpass sample trim filter ${PREFIX}1 ${PREFIX}2 ${PREFIX}s; do
+    for NAME in clumpify filteredbytile highpass sample trim filter screen ${PREFIX}1 ${PREFIX}2 ${PREFIX}s; do
         if [ ! -e ${NAME}.fq.gz ]; then
             continue;
         fi
@@ -56,11 +69,12 @@ for PREFIX in R S T; do
     done
 
     log_info "clear unneeded .fq.gz files"
-    for NAME in temp clumpify filteredbytile highpass sample trim filter; do
+    for NAME in temp clumpify filteredbytile highpass sample trim filter screen; do
         if [ -e ${NAME}.fq.gz ]; then
             rm ${NAME}.fq.gz
         fi
     done
+    rm -f screen.kraken.txt
 done
 
 for PREFIX in R S T; do
@@ -109,6 +123,30 @@ for PREFIX in R S T; do
     fi
 done
 
+{% if opt.screen != "0" -%}
+# Taxon proportions of contamination screening
+echo -e "Table: statScreen\n" > statScreen.md
+printf "| %s | %s | %s | %s |\n" \
+    "Name" "Taxon" "{% if opt.screener == "kraken2" %}Taxid{% else %}Shared{% endif %}" "Percent" \
+    >> statScreen.md
+printf "|:--|:--|--:|--:|\n" >> statScreen.md
+
+for PREFIX in R S T; do
+    if [ ! -e ${PREFIX}.screen.tsv ]; then
+        continue;
+    fi
+
+    cat ${PREFIX}.screen.tsv |
+        PREFIX=${PREFIX} perl -nla -F"\t" -e '
+            printf qq(| %s | %s | %s | %s |\n), $ENV{PREFIX}, @F;
+        ' \
+        >> statScreen.md
+done
+
+cat statScreen.md
+mv statScreen.md ../../
+
+{% endif -%}
 if [ -s statTrimReads.md ]; then
     cat statTrimReads.md
     mv statTrimReads.md ../../
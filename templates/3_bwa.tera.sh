@@ -5,6 +5,11 @@
 #----------------------------#
 log_warn 3_bwa.sh
 
+save_version bwa "bwa 2>&1 | grep Version"
+save_version samtools
+save_version picard "picard SortSam --version"
+save_version mosdepth
+
 if [ ! -e 1_genome/genome.fa ]; then
     log_info "1_genome/genome.fa does not exist"
     exit;
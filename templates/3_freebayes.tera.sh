@@ -5,6 +5,8 @@
 #----------------------------#
 log_warn 3_freebayes.sh
 
+save_version freebayes
+
 if [ ! -e 1_genome/genome.fa ]; then
     log_info "1_genome/genome.fa does not exist"
     exit;
@@ -0,0 +1,24 @@
+{%- include "header" -%}
+{# Keep a blank line #}
+#----------------------------#
+# Run
+#----------------------------#
+log_warn 3_long.sh
+
+mkdir -p 3_long
+
+if [ -e 3_long/long.fasta ]; then
+    log_debug "3_long/long.fasta presents"
+    exit;
+fi
+
+#----------------------------#
+# QC
+#----------------------------#
+log_info "Filter {% if opt.ont != "0" %}Nanopore{% else %}PacBio{% endif %} reads"
+
+# remove short reads; rename to serial numbers
+faops filter -l 0 -a {{ opt.longmin }} {{ opt.long }} stdout |
+    faops dazz -l 0 -p long stdin 3_long/long.fasta
+
+faops n50 -H -N 50 -S -C 3_long/long.fasta
@@ -5,6 +5,10 @@
 #----------------------------#
 log_warn 4_anchors.sh
 
+save_version bbtools bbversion.sh
+save_version dazz
+save_version spanr
+
 #----------------------------#
 # set parameters
 #----------------------------#
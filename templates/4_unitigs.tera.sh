@@ -5,6 +5,14 @@
 #----------------------------#
 log_warn {{ outname }}
 
+{% if unitigger == "bcalm" -%}
+save_version bcalm "bcalm -version"
+{% elif unitigger == "tadpole" -%}
+save_version bbtools bbversion.sh
+{% else -%}
+save_version superreads "create_k_unitigs_large_k --version"
+{% endif -%}
+
 parallel --no-run-if-empty --linebuffer -k -j 1 "
     if [ ! -e 4_down_sampling/Q{1}L{2}X{3}P{4}/pe.cor.fa ]; then
         exit;
@@ -5,6 +5,14 @@
 #----------------------------#
 log_warn {{ outname }}
 
+{% if unitigger == "bcalm" -%}
+save_version bcalm "bcalm -version"
+{% elif unitigger == "tadpole" -%}
+save_version bbtools bbversion.sh
+{% else -%}
+save_version superreads "create_k_unitigs_large_k --version"
+{% endif -%}
+
 parallel --no-run-if-empty --linebuffer -k -j 1 "
     if [ ! -e 6_down_sampling/MRX{1}P{2}/pe.cor.fa ]; then
         exit;
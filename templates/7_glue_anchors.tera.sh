@@ -5,10 +5,12 @@
 #----------------------------#
 log_warn 7_glue_anchors.sh
 
+save_version dazz
+
 #----------------------------#
 # set parameters
 #----------------------------#
-USAGE="Usage: $0 FILE_ANCHOR FILE_LONG GAP_COV"
+USAGE="Usage: $0 FILE_ANCHOR FILE_LONG GAP_COV [IDENTITY]"
 
 if [ "$#" -lt 2 ]; then
     echo >&2 "$USAGE"
@@ -18,6 +20,7 @@ fi
 FILE_ANCHOR=$1
 FILE_LONG=$2
 GAP_COV=${3:-3}
+IDENTITY=${4:-0.999}
 
 if [ -e 7_glue_anchors/contig.fasta ]; then
     echo >&2 "7_glue_anchors/contig.fasta presents"
@@ -36,7 +39,7 @@ dazz overlap2 \
     ${FILE_ANCHOR} \
     ${FILE_LONG} \
     -d 7_glue_anchors \
-    -b 50 --len 1000 --idt 0.999 --all
+    -b 50 --len 1000 --idt ${IDENTITY} --all
 
 cd 7_glue_anchors
 
@@ -81,7 +84,7 @@ dazz group \
     --oa anchor.ovlp.tsv \
     --parallel {{ opt.parallel }} \
     --range "1-$(faops n50 -H -N 0 -C anchor.fasta)" \
-    --len 1000 --idt 0.999 --max "-{{ opt.gluemin }}" -c ${GAP_COV}
+    --len 1000 --idt ${IDENTITY} --max "-{{ opt.gluemin }}" -c ${GAP_COV}
 
 log_info "Processing each groups"
 {% set parallel2 = opt.parallel | int / 2 -%}
@@ -91,7 +94,7 @@ cat group/groups.txt |
     parallel --no-run-if-empty --linebuffer -k -j {{ parallel2 }} '
         echo {};
         dazz orient \
-            --len 1000 --idt 0.999 \
+            --len 1000 --idt '${IDENTITY}' \
             group/{}.anchor.fasta \
             group/{}.long.fasta \
             -r group/{}.restrict.tsv \
@@ -5,6 +5,8 @@
 #----------------------------#
 log_warn 7_merge_anchors.sh
 
+save_version dazz
+
 #----------------------------#
 # set parameters
 #----------------------------#
@@ -61,6 +63,9 @@ anchr anchors \
     --readl {{ opt.readl }} \
     --uscale {{ opt.uscale }} \
     --lscale {{ opt.lscale }} \
+{%- if opt.organelle == "1" %}
+    --organelle \
+{%- endif %}
     -p {{ opt.parallel }} \
     --keepedge \
     --ratio 0.98 \
@@ -94,8 +99,9 @@ dazz contained \
     -o stdout |
     faops filter -a 500 -l 0 stdin ${DIR_MERGE}/others.intermediate_1.fasta
 
+# no others when all of them are contained
 cat ${DIR_MERGE}/others.intermediate_1.fasta |
-    grep '>infile_1/' |
+    { grep '>infile_1/' || true; } |
     sed 's/>//' \
     > ${DIR_MERGE}/others.txt
 
@@ -0,0 +1,171 @@
+{%- include "header" -%}
+{# Keep a blank line #}
+#----------------------------#
+# Run
+#----------------------------#
+log_warn 7_polish.sh
+
+save_version bwa "bwa 2>&1 | grep Version"
+save_version samtools
+save_version minimap2
+{% if opt.polisher == "pilon" -%}
+save_version pilon
+{% else -%}
+save_version racon
+{% endif -%}
+{# Keep a blank line #}
+#----------------------------#
+# set parameters
+#----------------------------#
+USAGE="Usage: $0 [FILE_CONTIG]"
+
+if [ "$#" -ge 1 ]; then
+    FILE_CONTIG=$1
+elif [ -e 7_fill_anchors/contig.fasta ]; then
+    FILE_CONTIG=7_fill_anchors/contig.fasta
+elif [ -e 7_glue_anchors/contig.fasta ]; then
+    FILE_CONTIG=7_glue_anchors/contig.fasta
+else
+    FILE_CONTIG=7_merge_anchors/anchor.merge.fasta
+fi
+
+if [ ! -e ${FILE_CONTIG} ]; then
+    log_info "${FILE_CONTIG} does not exist"
+    exit;
+fi
+
+# Convert to abs path
+FILE_CONTIG=$(readlinkf ${FILE_CONTIG})
+
+if [ -e 7_polish/contig.fasta ]; then
+    log_info "7_polish/contig.fasta presents"
+    exit;
+fi
+
+{% if opt.bwa != "0" %}{% set polish_reads = opt.bwa %}{% else %}{% set polish_reads = "trim" %}{% endif -%}
+{% set parallel2 = opt.parallel | int - 3 -%}
+{% if parallel2 < 2 %}{% set parallel2 = 2 %}{% endif -%}
+mkdir -p 7_polish
+cd 7_polish
+
+faops filter -l 0 ${FILE_CONTIG} round0.fasta
+
+{% if opt.polisher == "racon" -%}
+# racon needs unique names of reads
+if [ ! -e reads.fa ]; then
+{%- if opt.long != "0" %}
+    ln -s ../3_long/long.fasta reads.fa
+{%- else %}
+    gzip -dcf \
+        ../2_illumina/{{ polish_reads }}/R1.fq.gz \
+{%- if opt.se == "0" %}
+        ../2_illumina/{{ polish_reads }}/R2.fq.gz \
+{%- endif %}
+        | faops filter -l 0 stdin stdout |
+        faops dazz -l 0 -p read stdin reads.fa
+{%- endif %}
+fi
+
+{% endif -%}
+#----------------------------#
+# rounds
+#----------------------------#
+for ROUND in $(seq 1 {{ opt.rounds }}); do
+    PREV=round$((ROUND - 1))
+    CUR=round${ROUND}
+
+    if [ -e ${CUR}.fasta ]; then
+        log_debug "${CUR}.fasta presents"
+        continue;
+    fi
+
+    log_info "Polishing round ${ROUND} with {{ opt.polisher }}"
+{% if opt.polisher == "pilon" %}
+    # Illumina reads, the same options of bwa as 3_bwa.sh
+    bwa index ${PREV}.fasta
+    bwa mem -t {{ parallel2 }} \
+        -M -K 100000000 -v 3 -Y \
+        ${PREV}.fasta \
+        ../2_illumina/{{ polish_reads }}/R1.fq.gz \
+{%- if opt.se == "0" %}
+        ../2_illumina/{{ polish_reads }}/R2.fq.gz \
+{%- endif %}
+        2> ${CUR}.bwa.log |
+        samtools sort -@ 2 -o ${CUR}.R.bam -
+    samtools index ${CUR}.R.bam
+{% if opt.long != "0" %}
+    # long reads
+    minimap2 -t {{ parallel2 }} \
+        -ax {% if opt.ont != "0" %}map-ont{% else %}map-pb{% endif %} \
+        ${PREV}.fasta ../3_long/long.fasta |
+        samtools sort -@ 2 -o ${CUR}.L.bam -
+    samtools index ${CUR}.L.bam
+{% endif %}
+    pilon {% if opt.xmx != "0" %}-Xmx{{ opt.xmx }} {% endif %}\
+        --genome ${PREV}.fasta \
+        --frags ${CUR}.R.bam \
+{%- if opt.long != "0" %}
+        {% if opt.ont != "0" %}--nanopore{% else %}--pacbio{% endif %} ${CUR}.L.bam \
+{%- endif %}
+        --fix all \
+        --threads {{ opt.parallel }} \
+        --output ${CUR}.pilon
+
+    cat ${CUR}.pilon.fasta |
+        sed 's/_pilon$//' |
+        faops filter -l 0 stdin ${CUR}.fasta
+
+    rm -f ${PREV}.fasta.* ${CUR}.*.bam* ${CUR}.pilon.fasta
+{% else %}
+    minimap2 -t {{ parallel2 }} \
+        -x {% if opt.long != "0" %}{% if opt.ont != "0" %}map-ont{% else %}map-pb{% endif %}{% else %}sr{% endif %} \
+        ${PREV}.fasta reads.fa \
+        > ${CUR}.paf
+
+    racon -t {{ opt.parallel }} \
+        reads.fa ${CUR}.paf ${PREV}.fasta |
+        faops filter -l 0 stdin ${CUR}.fasta
+
+    rm -f ${CUR}.paf
+{% endif %}
+    # count substitutions and indels with the cs tag
+    minimap2 -t {{ parallel2 }} -cx asm5 --cs=short ${PREV}.fasta ${CUR}.fasta |
+        perl -nl -e '
+            m{cs:Z:(\S+)} or next;
+            $sum += () = $1 =~ m{[\*\+\-]}g;
+            END { print $sum || 0 }
+        ' \
+        > ${CUR}.changes.txt
+done
+
+ln -fs round{{ opt.rounds }}.fasta contig.fasta
+
+#----------------------------#
+# stats
+#----------------------------#
+# raw numbers, N50 and Sum in bp, changes of the input are empty
+tsv_row "name" "n50_bp" "sum_bp" "count" "changes" > ../statPolish.tsv
+tsv_row "input" "$( stat_raw round0.fasta )" "" >> ../statPolish.tsv
+for ROUND in $(seq 1 {{ opt.rounds }}); do
+    tsv_row \
+        "{{ opt.polisher }}.round${ROUND}" \
+        "$( stat_raw round${ROUND}.fasta )" \
+        "$( cat round${ROUND}.changes.txt )" \
+        >> ../statPolish.tsv
+done
+
+echo -e "Table: statPolish\n" > ../statPolish.md
+printf "| %s | %s | %s | %s | %s |\n" \
+    "Name" "N50" "Sum" "#" "Changes" \
+    >> ../statPolish.md
+printf "|:--|--:|--:|--:|--:|\n" >> ../statPolish.md
+sed 1d ../statPolish.tsv |
+    perl -nl -e 'print q(| ) . join(q( | ), map { length ? $_ : q(-) } split qq(\t), $_, -1) . q( |)' \
+    >> ../statPolish.md
+
+cat ../statPolish.md
+
+# statPolish.json
+anchr report --table ../statPolish.tsv
+
+exit 0;
@@ -0,0 +1,77 @@
+{%- include "header" -%}
+{# Keep a blank line #}
+#----------------------------#
+# Run
+#----------------------------#
+log_warn 8_canu.sh
+
+save_version canu
+save_version dazz
+
+#----------------------------#
+# set parameters
+#----------------------------#
+USAGE="Usage: $0 DIR_READS"
+
+DIR_READS=${1:-"2_illumina/trim"}
+
+# Convert to abs path
+DIR_READS="$(cd "$(dirname "$DIR_READS")"; pwd)/$(basename "$DIR_READS")"
+
+if [ -e 8_canu/anchor/anchor.fasta ]; then
+    log_info "8_canu/anchor/anchor.fasta presents"
+    exit;
+fi
+
+#----------------------------#
+# canu
+#----------------------------#
+if [ -e 8_canu/canu.non-contained.fasta ]; then
+    log_info "8_canu/canu.non-contained.fasta presents"
+else
+    log_info "Run canu"
+
+    mkdir -p 8_canu
+    cd 8_canu
+
+    canu \
+        -p canu -d . \
+        genomeSize={{ opt.genome }} \
+        maxThreads={{ opt.parallel }} \
+        useGrid=false \
+        {% if opt.ont != "0" %}-nanopore{% else %}-pacbio{% endif %} ${BASH_DIR}/3_long/long.fasta
+
+    dazz contained \
+        canu.contigs.fasta \
+        --len 1000 --idt 0.98 --proportion 0.99999 --parallel {{ opt.parallel }} \
+        -o stdout |
+        faops filter -a 1000 -l 0 stdin canu.non-contained.fasta
+
+    log_info "Clear intermediate files"
+    find . -type d -not -name "anchor" | parallel --no-run-if-empty -j 1 rm -fr
+fi
+
+#----------------------------#
+# anchor
+#----------------------------#
+log_info "Create anchors"
+
+cd ${BASH_DIR}
+mkdir -p 8_canu/anchor
+cd 8_canu/anchor
+
+anchr anchors \
+    ../canu.non-contained.fasta \
+    ${DIR_READS}/pe.cor.fa.gz \
+    --readl {{ opt.readl }} \
+    --uscale {{ opt.uscale }} \
+    --lscale {{ opt.lscale }} \
+{%- if opt.organelle == "1" %}
+    --organelle \
+{%- endif %}
+    -p {{ opt.parallel }} \
+    --ratio 0.98 \
+    -o anchors.sh
+bash anchors.sh
+
+exit 0;
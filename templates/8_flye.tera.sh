@@ -0,0 +1,82 @@
+{%- include "header" -%}
+{# Keep a blank line #}
+#----------------------------#
+# Run
+#----------------------------#
+log_warn 8_flye.sh
+
+save_version flye
+save_version dazz
+
+#----------------------------#
+# set parameters
+#----------------------------#
+USAGE="Usage: $0 DIR_READS"
+
+DIR_READS=${1:-"2_illumina/trim"}
+
+# Convert to abs path
+DIR_READS="$(cd "$(dirname "$DIR_READS")"; pwd)/$(basename "$DIR_READS")"
+
+if [ -e 8_flye/anchor/anchor.fasta ]; then
+    log_info "8_flye/anchor/anchor.fasta presents"
+    exit;
+fi
+
+#----------------------------#
+# flye
+#----------------------------#
+if [ -e 8_flye/flye.non-contained.fasta ]; then
+    log_info "8_flye/flye.non-contained.fasta presents"
+else
+    log_info "Run flye"
+
+    mkdir -p 8_flye
+    cd 8_flye
+
+    flye \
+        {% if opt.ont != "0" %}--nano-raw{% else %}--pacbio-raw{% endif %} ${BASH_DIR}/3_long/long.fasta \
+{%- if opt.meta == "1" %}
+        --meta \
+{%- else %}
+        --genome-size {{ opt.genome }} \
+{%- endif %}
+        --threads {{ opt.parallel }} \
+        --out-dir .
+
+    dazz contained \
+        assembly.fasta \
+        --len 1000 --idt 0.98 --proportion 0.99999 --parallel {{ opt.parallel }} \
+        -o stdout |
+        faops filter -a 1000 -l 0 stdin flye.non-contained.fasta
+
+    log_info "Clear intermediate files"
+    find . -type d -not -name "anchor" | parallel --no-run-if-empty -j 1 rm -fr
+fi
+
+{% if opt.meta != "1" -%}
+#----------------------------#
+# anchor
+#----------------------------#
+log_info "Create anchors"
+
+cd ${BASH_DIR}
+mkdir -p 8_flye/anchor
+cd 8_flye/anchor
+
+anchr anchors \
+    ../flye.non-contained.fasta \
+    ${DIR_READS}/pe.cor.fa.gz \
+    --readl {{ opt.readl }} \
+    --uscale {{ opt.uscale }} \
+    --lscale {{ opt.lscale }} \
+{%- if opt.organelle == "1" %}
+    --organelle \
+{%- endif %}
+    -p {{ opt.parallel }} \
+    --ratio 0.98 \
+    -o anchors.sh
+bash anchors.sh
+
+{% endif -%}
+exit 0;
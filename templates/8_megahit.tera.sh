@@ -5,6 +5,9 @@
 #----------------------------#
 log_warn 8_megahit.sh
 
+save_version megahit
+save_version dazz
+
 #----------------------------#
 # set parameters
 #----------------------------#
@@ -30,9 +33,14 @@ else
 
     megahit \
         -t {{ opt.parallel }} \
+{%- if opt.meta == "1" %}
+        --presets meta-large \
+        --12 ${DIR_READS}/pe.cor.fa.gz \
+{%- else %}
         --k-list 31,41,51,61,71,81 \
         --12 ${DIR_READS}/pe.cor.fa.gz \
         --min-count 3 \
+{%- endif %}
         -o 8_megahit
 
     dazz contained \
@@ -45,6 +53,7 @@ else
     find . -type d -path "*8_megahit/*" -not -name "anchor" | parallel --no-run-if-empty -j 1 rm -fr
 fi
 
+{% if opt.meta != "1" -%}
 #----------------------------#
 # anchor
 #----------------------------#
@@ -59,9 +68,13 @@ anchr anchors \
     --readl {{ opt.readl }} \
     --uscale {{ opt.uscale }} \
     --lscale {{ opt.lscale }} \
+{%- if opt.organelle == "1" %}
+    --organelle \
+{%- endif %}
     -p {{ opt.parallel }} \
     --ratio 0.98 \
     -o anchors.sh
 bash anchors.sh
 
+{% endif -%}
 exit 0;
@@ -5,6 +5,9 @@
 #----------------------------#
 log_warn 8_mr_megahit.sh
 
+save_version megahit
+save_version dazz
+
 #----------------------------#
 # set parameters
 #----------------------------#
@@ -25,9 +28,14 @@ else
 
     megahit \
         -t {{ opt.parallel }} \
+{%- if opt.meta == "1" %}
+        --presets meta-large \
+        --12 ${BASH_DIR}/2_illumina/merge/pe.cor.fa.gz \
+{%- else %}
         --k-min 45 --k-max 225 --k-step 26 \
         --12 ${BASH_DIR}/2_illumina/merge/pe.cor.fa.gz \
         --min-count 3 \
+{%- endif %}
         -o 8_mr_megahit
 
     dazz contained \
@@ -40,6 +48,7 @@ else
     find . -type d -path "*8_mr_megahit/*" -not -name "anchor" | parallel --no-run-if-empty -j 1 rm -fr
 fi
 
+{% if opt.meta != "1" -%}
 #----------------------------#
 # anchor
 #----------------------------#
@@ -54,6 +63,9 @@ anchr anchors \
     --readl {{ opt.readl }} \
     --uscale {{ opt.uscale }} \
     --lscale {{ opt.lscale }} \
+{%- if opt.organelle == "1" %}
+    --organelle \
+{%- endif %}
     -p {{ opt.parallel }} \
     --ratio 0.98 \
     -o anchors.sh
@@ -62,4 +74,5 @@ bash anchors.sh
 find . -type f -name "pe.anchor.fa" | xargs rm
 find . -type f -name "anchor.*.fasta" | xargs rm
 
+{% endif -%}
 exit 0;
@@ -5,6 +5,10 @@
 #----------------------------#
 log_warn 8_mr_spades.sh
 
+save_version anchr
+save_version spades.py
+save_version dazz
+
 #----------------------------#
 # set parameters
 #----------------------------#
@@ -27,23 +31,25 @@ else
     cd 8_mr_spades
 
     mkdir -p re-pair
-    faops filter -l 0 -a 60 ${BASH_DIR}/2_illumina/merge/pe.cor.fa.gz stdout |
-        repair.sh \
-            in=stdin.fa \
-            out=re-pair/R1.fa \
-            out2=re-pair/R2.fa \
-            outs=re-pair/Rs.fa \
-            threads={{ opt.parallel }} \
-            fint overwrite
+    anchr reads filter -a 60 ${BASH_DIR}/2_illumina/merge/pe.cor.fa.gz |
+        anchr reads repair stdin \
+            --out1 re-pair/R1.fa \
+            --out2 re-pair/R2.fa \
+            --outs re-pair/Rs.fa
 
     # spades seems ignore non-properly paired reads
     spades.py \
         -t {{ opt.parallel }} \
+{%- if opt.meta == "1" %}
+        --meta \
+{%- endif %}
         --only-assembler \
         -k 25,55,95,125 \
         -1 re-pair/R1.fa \
         -2 re-pair/R2.fa \
+{%- if opt.meta != "1" %}
         -s re-pair/Rs.fa \
+{%- endif %}
         -o .
 
     dazz contained \
@@ -56,6 +62,7 @@ else
     find . -type d -not -name "anchor" | parallel --no-run-if-empty -j 1 rm -fr
 fi
 
+{% if opt.meta != "1" -%}
 #----------------------------#
 # anchor
 #----------------------------#
@@ -71,9 +78,13 @@ anchr anchors \
     --readl {{ opt.readl }} \
     --uscale {{ opt.uscale }} \
     --lscale {{ opt.lscale }} \
+{%- if opt.organelle == "1" %}
+    --organelle \
+{%- endif %}
     -p {{ opt.parallel }} \
     --ratio 0.98 \
     -o anchors.sh
 bash anchors.sh
 
+{% endif -%}
 exit 0;
@@ -5,6 +5,10 @@
 #----------------------------#
 log_warn 8_platanus.sh
 
+save_version anchr
+save_version platanus "platanus 2>&1 | grep -i version"
+save_version dazz
+
 #----------------------------#
 # set parameters
 #----------------------------#
@@ -30,13 +34,10 @@ if [ -e platanus.non-contained.fasta ]; then
 else
     log_info "Run platanus"
 
-    faops filter -l 0 -a 60 ${DIR_READS}/pe.cor.fa.gz stdout |
-        repair.sh \
-            in=stdin.fa \
-            out=pe.fa \
-            outs=se.fa \
-            threads={{ opt.parallel }} \
-            fint overwrite
+    anchr reads filter -a 60 ${DIR_READS}/pe.cor.fa.gz |
+        anchr reads repair stdin \
+            --out1 pe.fa \
+            --outs se.fa
 
     if [ -s pe.fa ]; then
         platanus assemble -t {{ opt.parallel }} -m 100 \
@@ -94,6 +95,9 @@ anchr anchors \
     --readl {{ opt.readl }} \
     --uscale {{ opt.uscale }} \
     --lscale {{ opt.lscale }} \
+{%- if opt.organelle == "1" %}
+    --organelle \
+{%- endif %}
     -p {{ opt.parallel }} \
     --ratio 0.98 \
     -o anchors.sh
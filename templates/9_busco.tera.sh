@@ -4,6 +4,8 @@
 # Run
 #----------------------------#
 log_warn 9_busco.sh
+
+save_version busco
 {% set unitiggers = opt.unitigger | split(pat=" ") -%}
 
 ARRAY=()
@@ -40,6 +42,9 @@ fi
 if [ -e 7_fill_anchors/contig.fasta ]; then
     ARRAY+=('fill_anchors::7_fill_anchors/contig.fasta')
 fi
+if [ -e 7_polish/contig.fasta ]; then
+    ARRAY+=('polish::7_polish/contig.fasta')
+fi
 
 if [ -e 8_spades/spades.non-contained.fasta ]; then
     ARRAY+=('spades::8_spades/spades.non-contained.fasta')
@@ -59,6 +64,13 @@ if [ -e 8_platanus/platanus.non-contained.fasta ]; then
     ARRAY+=('platanus::8_platanus/platanus.non-contained.fasta')
 fi
 
+if [ -e 8_flye/flye.non-contained.fasta ]; then
+    ARRAY+=('flye::8_flye/flye.non-contained.fasta')
+fi
+if [ -e 8_canu/canu.non-contained.fasta ]; then
+    ARRAY+=('canu::8_canu/canu.non-contained.fasta')
+fi
+
 mkdir -p 9_busco
 
 for item in "${ARRAY[@]}" ; do
@@ -0,0 +1,174 @@
+{%- include "header" -%}
+{# Keep a blank line #}
+#----------------------------#
+# Run
+#----------------------------#
+log_warn 9_merqury.sh
+
+save_version meryl
+save_version minimap2
+save_version samtools
+
+{% set parallel2 = opt.parallel | int - 3 -%}
+{% if parallel2 < 2 %}{% set parallel2 = 2 %}{% endif -%}
+# Names are the same as statFinal.md
+ARRAY=()
+
+if [ -e 7_merge_anchors/anchor.merge.fasta ]; then
+    ARRAY+=('7_merge_anchors.anchors::7_merge_anchors/anchor.merge.fasta')
+fi
+
+if [ -e 7_glue_anchors/contig.fasta ]; then
+    ARRAY+=('glue_anchors::7_glue_anchors/contig.fasta')
+fi
+if [ -e 7_fill_anchors/contig.fasta ]; then
+    ARRAY+=('fill_anchors::7_fill_anchors/contig.fasta')
+fi
+if [ -e 7_polish/contig.fasta ]; then
+    ARRAY+=('polish::7_polish/contig.fasta')
+fi
+
+if [ -e 8_spades/spades.non-contained.fasta ]; then
+    ARRAY+=('spades.non-contained::8_spades/spades.non-contained.fasta')
+fi
+if [ -e 8_mr_spades/spades.non-contained.fasta ]; then
+    ARRAY+=('mr_spades.non-contained::8_mr_spades/spades.non-contained.fasta')
+fi
+
+if [ -e 8_megahit/megahit.non-contained.fasta ]; then
+    ARRAY+=('megahit.non-contained::8_megahit/megahit.non-contained.fasta')
+fi
+if [ -e 8_mr_megahit/megahit.non-contained.fasta ]; then
+    ARRAY+=('mr_megahit.non-contained::8_mr_megahit/megahit.non-contained.fasta')
+fi
+
+if [ -e 8_platanus/platanus.non-contained.fasta ]; then
+    ARRAY+=('platanus.non-contained::8_platanus/platanus.non-contained.fasta')
+fi
+
+if [ -e 8_flye/flye.non-contained.fasta ]; then
+    ARRAY+=('flye.non-contained::8_flye/flye.non-contained.fasta')
+fi
+if [ -e 8_canu/canu.non-contained.fasta ]; then
+    ARRAY+=('canu.non-contained::8_canu/canu.non-contained.fasta')
+fi
+
+mkdir -p 9_merqury
+cd 9_merqury
+
+#----------------------------#
+# k-mers of trimmed reads
+#----------------------------#
+if [ ! -e reads.meryl ]; then
+    meryl count k=21 threads={{ opt.parallel }} \
+        ../2_illumina/trim/R1.fq.gz \
+{%- if opt.se == "0" %}
+        ../2_illumina/trim/R2.fq.gz \
+{%- endif %}
+        output reads.meryl
+fi
+
+# Solid k-mers are above the trough of erroneous ones
+if [ ! -e solid.meryl ]; then
+    meryl histogram reads.meryl > reads.hist
+    THRESHOLD=$( anchr kmer-profile reads.hist -k 21 | jq '.ERROR_THRESHOLD | tonumber' )
+    log_debug "Solid k-mers: >= ${THRESHOLD}"
+
+    meryl greater-than $(( THRESHOLD - 1 )) reads.meryl output solid.meryl
+fi
+
+# "present" is the number of k-mers with multiplicity, "distinct" is not
+meryl_count () {
+    meryl statistics "$1" |
+        perl -nl -e "m{^\s*$2\s+(\d+)} and print \$1"
+}
+
+SOLID=$( meryl_count solid.meryl distinct )
+
+#----------------------------#
+# QV, completeness and mapped reads of each assembly
+#----------------------------#
+for item in "${ARRAY[@]}" ; do
+    NAME="${item%%::*}"
+    FILE="${item##*::}"
+
+    if [ -e ${NAME}.tsv ]; then
+        continue;
+    fi
+
+    log_info "${NAME}"
+
+    meryl count k=21 threads={{ opt.parallel }} ../${FILE} output ${NAME}.meryl
+    meryl difference ${NAME}.meryl reads.meryl output ${NAME}.only.meryl
+    meryl intersect ${NAME}.meryl solid.meryl output ${NAME}.solid.meryl
+
+    # https://github.com/marbl/merqury/blob/master/eval/qv.sh
+    ASM_ONLY=$( meryl_count ${NAME}.only.meryl present )
+    TOTAL=$( meryl_count ${NAME}.meryl present )
+    FOUND=$( meryl_count ${NAME}.solid.meryl distinct )
+
+    MAPPED=$(
+        minimap2 -t {{ parallel2 }} -ax sr --secondary=no \
+            ../${FILE} \
+            ../2_illumina/trim/R1.fq.gz \
+{%- if opt.se == "0" %}
+            ../2_illumina/trim/R2.fq.gz \
+{%- endif %}
+            2> /dev/null |
+            samtools flagstat - |
+            perl -nl -e 'm{ mapped \(([\d.]+)%} and print $1 and exit'
+    )
+
+    # a row of statMerqury.tsv, QV is empty for an empty assembly
+    perl -e '
+        my ($name, $asm_only, $total, $found, $solid, $mapped) = @ARGV;
+        my $qv = q();
+        if ($asm_only > 0) {
+            my $error = 1 - (1 - $asm_only / $total) ** (1 / 21);
+            $qv = sprintf q(%.1f), -10 * log($error) / log(10);
+        }
+        elsif ($total > 0) {
+            $qv = q(inf);
+        }
+        printf qq(%s\t%s\t%.2f\t%s\n),
+            $name, $qv, $solid ? $found / $solid * 100 : 0, $mapped || 0;
+    ' ${NAME} ${ASM_ONLY} ${TOTAL} ${FOUND} ${SOLID} ${MAPPED} \
+        > ${NAME}.tsv
+
+    rm -fr ${NAME}.meryl ${NAME}.only.meryl ${NAME}.solid.meryl
+done
+
+#----------------------------#
+# stats
+#----------------------------#
+# raw numbers, percentages without `%`
+tsv_row "name" "qv" "completeness_pct" "mapped_pct" > ../statMerqury.tsv
+for item in "${ARRAY[@]}" ; do
+    NAME="${item%%::*}"
+
+    if [ -e ${NAME}.tsv ]; then
+        cat ${NAME}.tsv >> ../statMerqury.tsv
+    fi
+done
+
+echo -e "Table: statMerqury\n" > ../statMerqury.md
+printf "| %s | %s | %s | %s |\n" \
+    "Name" "QV" "Completeness" "Mapped%" \
+    >> ../statMerqury.md
+printf "|:--|--:|--:|--:|\n" >> ../statMerqury.md
+sed 1d ../statMerqury.tsv |
+    perl -nla -F"\t" -e '
+        my ( $name, $qv, $completeness, $mapped ) = @F;
+        printf qq(| %s | %s | %s%% | %s%% |\n),
+            $name, length $qv ? $qv : q(-), $completeness, $mapped;
+    ' \
+    >> ../statMerqury.md
+
+cat ../statMerqury.md
+
+# statMerqury.json
+anchr report --table ../statMerqury.tsv
+
+log_info Done.
+
+exit 0
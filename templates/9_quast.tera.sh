@@ -4,6 +4,8 @@
 # Run
 #----------------------------#
 log_warn 9_quast.sh
+
+save_version quast
 {% set unitiggers = opt.unitigger | split(pat=" ") -%}
 
 QUAST_TARGET=
@@ -40,6 +42,10 @@ if [ -e 7_fill_anchors/contig.fasta ]; then
     QUAST_TARGET+=" 7_fill_anchors/contig.fasta "
     QUAST_LABEL+="fill_anchors,"
 fi
+if [ -e 7_polish/contig.fasta ]; then
+    QUAST_TARGET+=" 7_polish/contig.fasta "
+    QUAST_LABEL+="polish,"
+fi
 
 if [ -e 8_spades/spades.non-contained.fasta ]; then
     QUAST_TARGET+=" 8_spades/spades.non-contained.fasta "
@@ -64,6 +70,15 @@ if [ -e 8_platanus/platanus.non-contained.fasta ]; then
     QUAST_LABEL+="platanus,"
 fi
 
+if [ -e 8_flye/flye.non-contained.fasta ]; then
+    QUAST_TARGET+=" 8_flye/flye.non-contained.fasta "
+    QUAST_LABEL+="flye,"
+fi
+if [ -e 8_canu/canu.non-contained.fasta ]; then
+    QUAST_TARGET+=" 8_canu/canu.non-contained.fasta "
+    QUAST_LABEL+="canu,"
+fi
+
 if [ -e 1_genome/paralogs.fa ]; then
     QUAST_TARGET+=" 1_genome/paralogs.fa "
     QUAST_LABEL+="paralogs,"
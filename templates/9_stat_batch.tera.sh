@@ -0,0 +1,21 @@
+{%- include "header" -%}
+{# Keep a blank line #}
+#----------------------------#
+# Run
+#----------------------------#
+log_warn 9_stat_batch.sh
+
+PROJECTS=(
+{%- for s in samples %}
+    {{ s }}
+{%- endfor %}
+)
+
+# stat*.tsv of all projects, one value per row
+anchr report --merge -o statBatch.tsv "${PROJECTS[@]}"
+
+# One row per project and name
+anchr report --merge --stat statReads -o statBatchReads.tsv "${PROJECTS[@]}"
+anchr report --merge --stat statFinal -o statBatchFinal.tsv "${PROJECTS[@]}"
+
+cat statBatchFinal.tsv
@@ -5,108 +5,107 @@
 #----------------------------#
 log_warn 9_statFinal.sh
 
-echo -e "Table: statFinal\n" > statFinal.md
-printf "| %s | %s | %s | %s |\n" \
-    "Name" "N50" "Sum" "#" \
-    >> statFinal.md
-printf "|:--|--:|--:|--:|\n" >> statFinal.md
+# raw numbers, N50 and Sum in bp
+tsv_row "name" "n50_bp" "sum_bp" "count" > statFinal.tsv
+
+stat_final () {
+    if [ -e "$2" ]; then
+        tsv_row "$1" "$( stat_raw "$2" )" >> statFinal.tsv
+    fi
+}
 
 # genome
-if [ -e 1_genome/genome.fa ]; then
-    printf "| %s | %s | %s | %s |\n" \
-        $(echo "Genome";   faops n50 -H -S -C 1_genome/genome.fa;) >> statFinal.md
-fi
-if [ -e 1_genome/paralogs.fa ]; then
-    printf "| %s | %s | %s | %s |\n" \
-        $(echo "Paralogs"; faops n50 -H -S -C 1_genome/paralogs.fa;) >> statFinal.md
-fi
-if [ -e 1_genome/repetitives.fa ]; then
-    printf "| %s | %s | %s | %s |\n" \
-        $(echo "Repetitives"; faops n50 -H -S -C 1_genome/repetitives.fa;) >> statFinal.md
-fi
+stat_final "Genome" 1_genome/genome.fa
+stat_final "Paralogs" 1_genome/paralogs.fa
+stat_final "Repetitives" 1_genome/repetitives.fa
 
 # anchors
-for D in 7_merge_anchors; do
-    if [ -e ${D}/anchor.merge.fasta ]; then
-        printf "| %s | %s | %s | %s |\n" \
-            $(echo "${D}.anchors";   faops n50 -H -S -C ${D}/anchor.merge.fasta;) >> statFinal.md
-    fi
-    if [ -e ${D}/others.non-contained.fasta ]; then
-        printf "| %s | %s | %s | %s |\n" \
-            $(echo "${D}.others";   faops n50 -H -S -C ${D}/others.non-contained.fasta;) >> statFinal.md
-    fi
-done
+stat_final "7_merge_anchors.anchors" 7_merge_anchors/anchor.merge.fasta
+stat_final "7_merge_anchors.others" 7_merge_anchors/others.non-contained.fasta
 
 # extended anchors
-if [ -e 7_glue_anchors/contig.fasta ]; then
-    printf "| %s | %s | %s | %s |\n" \
-        $(echo "glue_anchors"; faops n50 -H -S -C 7_glue_anchors/contig.fasta;) >> statFinal.md
-fi
-if [ -e 7_fill_anchors/contig.fasta ]; then
-    printf "| %s | %s | %s | %s |\n" \
-        $(echo "fill_anchors"; faops n50 -H -S -C 7_fill_anchors/contig.fasta;) >> statFinal.md
-fi
+stat_final "glue_anchors" 7_glue_anchors/contig.fasta
+stat_final "fill_anchors" 7_fill_anchors/contig.fasta
+
+# polished
+stat_final "polish" 7_polish/contig.fasta
 
 # spades
-if [ -e 8_spades/contigs.fasta ]; then
-    printf "| %s | %s | %s | %s |\n" \
-        $(echo "spades.contig"; faops n50 -H -S -C 8_spades/contigs.fasta;) >> statFinal.md
-fi
-if [ -e 8_spades/scaffolds.fasta ]; then
-    printf "| %s | %s | %s | %s |\n" \
-        $(echo "spades.scaffold"; faops n50 -H -S -C 8_spades/scaffolds.fasta;) >> statFinal.md
-fi
-if [ -e 8_spades/spades.non-contained.fasta ]; then
-    printf "| %s | %s | %s | %s |\n" \
-        $(echo "spades.non-contained"; faops n50 -H -S -C 8_spades/spades.non-contained.fasta;) >> statFinal.md
-fi
+stat_final "spades.contig" 8_spades/contigs.fasta
+stat_final "spades.scaffold" 8_spades/scaffolds.fasta
+stat_final "spades.non-contained" 8_spades/spades.non-contained.fasta
 
 # mr_spades
-if [ -e 8_mr_spades/contigs.fasta ]; then
-    printf "| %s | %s | %s | %s |\n" \
-        $(echo "mr_spades.contig"; faops n50 -H -S -C 8_mr_spades/contigs.fasta;) >> statFinal.md
-fi
-if [ -e 8_mr_spades/scaffolds.fasta ]; then
-    printf "| %s | %s | %s | %s |\n" \
-        $(echo "mr_spades.scaffold"; faops n50 -H -S -C 8_mr_spades/scaffolds.fasta;) >> statFinal.md
-fi
-if [ -e 8_mr_spades/spades.non-contained.fasta ]; then
-    printf "| %s | %s | %s | %s |\n" \
-        $(echo "mr_spades.non-contained"; faops n50 -H -S -C 8_mr_spades/spades.non-contained.fasta;) >> statFinal.md
-fi
+stat_final "mr_spades.contig" 8_mr_spades/contigs.fasta
+stat_final "mr_spades.scaffold" 8_mr_spades/scaffolds.fasta
+stat_final "mr_spades.non-contained" 8_mr_spades/spades.non-contained.fasta
 
 # megahit
-if [ -e 8_megahit/final.contigs.fa ]; then
-    printf "| %s | %s | %s | %s |\n" \
-        $(echo "megahit.contig"; faops n50 -H -S -C 8_megahit/final.contigs.fa;) >> statFinal.md
-fi
-if [ -e 8_megahit/megahit.non-contained.fasta ]; then
-    printf "| %s | %s | %s | %s |\n" \
-        $(echo "megahit.non-contained"; faops n50 -H -S -C 8_megahit/megahit.non-contained.fasta;) >> statFinal.md
-fi
+stat_final "megahit.contig" 8_megahit/final.contigs.fa
+stat_final "megahit.non-contained" 8_megahit/megahit.non-contained.fasta
 
 # mr_megahit
-if [ -e 8_mr_megahit/final.contigs.fa ]; then
-    printf "| %s | %s | %s | %s |\n" \
-        $(echo "mr_megahit.contig"; faops n50 -H -S -C 8_mr_megahit/final.contigs.fa;) >> statFinal.md
-fi
-if [ -e 8_mr_megahit/megahit.non-contained.fasta ]; then
-    printf "| %s | %s | %s | %s |\n" \
-        $(echo "mr_megahit.non-contained"; faops n50 -H -S -C 8_mr_megahit/megahit.non-contained.fasta;) >> statFinal.md
-fi
+stat_final "mr_megahit.contig" 8_mr_megahit/final.contigs.fa
+stat_final "mr_megahit.non-contained" 8_mr_megahit/megahit.non-contained.fasta
 
 # platanus
-if [ -e 8_platanus/out_contig.fa ]; then
-	printf "| %s | %s | %s | %s |\n" \
-	    $(echo "platanus.contig"; faops n50 -H -S -C 8_platanus/out_contig.fa;) >> statFinal.md
-fi
-if [ -e 8_platanus/out_gapClosed.fa ]; then
-	printf "| %s | %s | %s | %s |\n" \
-	    $(echo "platanus.scaffold"; faops n50 -H -S -C 8_platanus/out_gapClosed.fa;) >> statFinal.md
-fi
-if [ -e 8_platanus/platanus.non-contained.fasta ]; then
-	printf "| %s | %s | %s | %s |\n" \
-	    $(echo "platanus.non-contained"; faops n50 -H -S -C 8_platanus/platanus.non-contained.fasta;) >> statFinal.md
+stat_final "platanus.contig" 8_platanus/out_contig.fa
+stat_final "platanus.scaffold" 8_platanus/out_gapClosed.fa
+stat_final "platanus.non-contained" 8_platanus/platanus.non-contained.fasta
+
+# flye
+stat_final "flye.contig" 8_flye/assembly.fasta
+stat_final "flye.non-contained" 8_flye/flye.non-contained.fasta
+
+# canu
+stat_final "canu.contig" 8_canu/canu.contigs.fasta
+stat_final "canu.non-contained" 8_canu/canu.non-contained.fasta
+
+# QV, k-mer completeness and mapped reads from 9_merqury.sh
+if [ -e statMerqury.tsv ]; then
+    perl -nl -i -e '
+        BEGIN {
+            our %stat_of;
+            open my $fh, q(<), q(statMerqury.tsv);
+            while ( my $line = <$fh> ) {
+                chomp $line;
+                my @fields = split qq(\t), $line, -1;
+                $stat_of{ $fields[0] } = [ @fields[ 1 .. 3 ] ];
+            }
+        }
+
+        if ( $. == 1 ) {
+            print join qq(\t), $_, qw(qv completeness_pct mapped_pct);
+        }
+        else {
+            my ($name) = split qq(\t);
+            my $stat = $stat_of{$name} || [ q(), q(), q() ];
+            print join qq(\t), $_, @{$stat};
+        }
+    ' statFinal.tsv
 fi
 
+echo -e "Table: statFinal\n" > statFinal.md
+if [ -e statMerqury.tsv ]; then
+    printf "| %s | %s | %s | %s | %s | %s | %s |\n" \
+        "Name" "N50" "Sum" "#" "QV" "Completeness" "Mapped%" \
+        >> statFinal.md
+    printf "|:--|--:|--:|--:|--:|--:|--:|\n" >> statFinal.md
+else
+    printf "| %s | %s | %s | %s |\n" \
+        "Name" "N50" "Sum" "#" \
+        >> statFinal.md
+    printf "|:--|--:|--:|--:|\n" >> statFinal.md
+fi
+sed 1d statFinal.tsv |
+    perl -nl -e '
+        my @row = map { length ? $_ : q(-) } split qq(\t), $_, -1;
+        $row[$_] .= q(%) for grep { $row[$_] ne q(-) } 5 .. $#row;
+        print q(| ) . join(q( | ), @row) . q( |);
+    ' \
+    >> statFinal.md
+
 cat statFinal.md
+
+# statFinal.json
+anchr report --table statFinal.tsv
@@ -24,6 +24,19 @@ printf "| %s | %s | %s | %s | %s | %s | %s | %s | %s | %s | %s | %s | %s |\n" \
 printf "|:--|--:|--:|--:|--:|--:|--:|--:|--:|--:|--:|--:|--:|\n" \
     >> ${FILENAME_MD}
 
+# raw numbers, N50 and Sum in bp, runtimes in seconds
+FILENAME_TSV=${FILENAME_MD%.md}.tsv
+tsv_row \
+    "name" "mapped_ratio" \
+    "n50_anchor_bp" "sum_anchor_bp" "count_anchor" \
+    "n50_others_bp" "sum_others_bp" "count_others" \
+    "median" "mad" "lower" "upper" \
+    "runtime_an_s" \
+    > ${FILENAME_TSV}
+
+# rows are written inside directories of each part
+exec 3>> ${FILENAME_TSV}
+
 for D in $( find . -type d -name "${DIR_PREFIX}*" | sort ); do
 	if [ ! -e ${D}/anchor.merge.fasta ]; then
 		continue;
@@ -45,8 +58,25 @@ for D in $( find . -type d -name "${DIR_PREFIX}*" | sort ); do
 		$( cat anchor/env.json | jq '.upper | tonumber' ) \
         $( time_format ${SECS_AN} )
 
+	tsv_row \
+	    "$(basename "${D}")" \
+	    "${MAPPED_RATIO}" \
+	    "$( stat_raw anchor.merge.fasta )" \
+	    "$( stat_raw others.non-contained.fasta )" \
+	    "$( jq -r '.median // empty' anchor/env.json )" \
+	    "$( jq -r '.MAD // empty' anchor/env.json )" \
+	    "$( jq -r '.lower // empty' anchor/env.json )" \
+	    "$( jq -r '.upper // empty' anchor/env.json )" \
+	    "${SECS_AN}" \
+	    >&3
+
 	popd > /dev/null
 done \
 >> ${FILENAME_MD}
 
+exec 3>&-
+
 cat ${FILENAME_MD}
+
+# JSON next to the tables
+anchr report --table ${FILENAME_TSV}
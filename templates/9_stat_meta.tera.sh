@@ -0,0 +1,158 @@
+{%- include "header" -%}
+{# Keep a blank line #}
+#----------------------------#
+# Run
+#----------------------------#
+log_warn 9_stat_meta.sh
+
+save_version bbtools bbversion.sh
+
+# Names are the same as statFinal.md
+ARRAY=()
+
+if [ -e 8_spades/spades.non-contained.fasta ]; then
+    ARRAY+=('spades.non-contained::8_spades/spades.non-contained.fasta')
+fi
+if [ -e 8_mr_spades/spades.non-contained.fasta ]; then
+    ARRAY+=('mr_spades.non-contained::8_mr_spades/spades.non-contained.fasta')
+fi
+
+if [ -e 8_megahit/megahit.non-contained.fasta ]; then
+    ARRAY+=('megahit.non-contained::8_megahit/megahit.non-contained.fasta')
+fi
+if [ -e 8_mr_megahit/megahit.non-contained.fasta ]; then
+    ARRAY+=('mr_megahit.non-contained::8_mr_megahit/megahit.non-contained.fasta')
+fi
+
+if [ -e 8_flye/flye.non-contained.fasta ]; then
+    ARRAY+=('flye.non-contained::8_flye/flye.non-contained.fasta')
+fi
+
+mkdir -p 9_meta
+cd 9_meta
+
+#----------------------------#
+# Coverage of contigs
+#----------------------------#
+for item in "${ARRAY[@]}" ; do
+    NAME="${item%%::*}"
+    FILE="${item##*::}"
+
+    if [ -e ${NAME}.covstats.txt ]; then
+        continue;
+    fi
+
+    log_info "${NAME}"
+
+    bbmap.sh \
+        ref=../${FILE} \
+        in=../2_illumina/trim/pe.cor.fa.gz \
+        nodisk fast ambiguous=random \
+        covstats=${NAME}.covstats.txt \
+        threads={{ opt.parallel }}{% if opt.xmx != "0" %} -Xmx{{ opt.xmx }}{% endif %} \
+        1>${NAME}.bbmap.err 2>&1
+done
+
+#----------------------------#
+# Bins of coverage and GC
+#----------------------------#
+# Coverages are binned by powers of 2 and GC contents by 0.1
+# Contigs of one organism fall into one or a few adjacent bins
+# cov and gc are lower bounds of bins, N50 and Sum in bp
+tsv_row "name" "cov" "gc" "n50_bp" "sum_bp" "count" > ../statMeta.tsv
+
+for item in "${ARRAY[@]}" ; do
+    NAME="${item%%::*}"
+    FILE="${item##*::}"
+
+    if [ ! -e ${NAME}.covstats.txt ]; then
+        continue;
+    fi
+
+    perl -e '
+        my ( $name, $file, $covstats ) = @ARGV;
+
+        # #ID Avg_fold Length ...
+        my %cov_of;
+        open my $cov_fh, q(<), $covstats;
+        while (<$cov_fh>) {
+            /^#/ and next;
+            my @F = split /\t/;
+            my ($id) = split /\s+/, $F[0];
+            $cov_of{$id} = $F[1];
+        }
+
+        my ( @names, %seq_of );
+        open my $fa_fh, q(<), $file;
+        while (<$fa_fh>) {
+            chomp;
+            if (/^>(\S+)/) {
+                push @names, $1;
+                $seq_of{$1} = q();
+            }
+            else {
+                $seq_of{ $names[-1] } .= $_;
+            }
+        }
+
+        my %lengths_of;
+        for my $id (@names) {
+            my $seq = $seq_of{$id};
+            my $cov = $cov_of{$id} || 0;
+            my $acgt = ( $seq =~ tr/ACGTacgt// );
+            my $gc = $acgt ? ( $seq =~ tr/GCgc// ) / $acgt : 0;
+
+            my $cov_bin = $cov < 1 ? 0 : 2 ** int( log($cov) / log(2) );
+            my $gc_bin = int( $gc * 10 ) / 10;
+            $gc_bin = 0.9 if $gc_bin > 0.9;
+
+            push @{ $lengths_of{$cov_bin}{$gc_bin} }, length $seq;
+        }
+
+        for my $cov_bin ( sort { $b <=> $a } keys %lengths_of ) {
+            for my $gc_bin ( sort { $a <=> $b } keys %{ $lengths_of{$cov_bin} } ) {
+                my @lengths = sort { $b <=> $a } @{ $lengths_of{$cov_bin}{$gc_bin} };
+
+                my $sum = 0;
+                $sum += $_ for @lengths;
+                my ( $acc, $n50 ) = ( 0, 0 );
+                for (@lengths) {
+                    $acc += $_;
+                    if ( $acc * 2 >= $sum ) {
+                        $n50 = $_;
+                        last;
+                    }
+                }
+
+                printf qq(%s\t%d\t%.1f\t%d\t%d\t%d\n),
+                    $name, $cov_bin, $gc_bin, $n50, $sum, scalar @lengths;
+            }
+        }
+    ' ${NAME} ../${FILE} ${NAME}.covstats.txt \
+        >> ../statMeta.tsv
+done
+
+echo -e "Table: statMeta\n" > ../statMeta.md
+printf "| %s | %s | %s | %s | %s | %s |\n" \
+    "Name" "Coverage" "GC" "N50" "Sum" "#" \
+    >> ../statMeta.md
+printf "|:--|--:|--:|--:|--:|--:|\n" >> ../statMeta.md
+sed 1d ../statMeta.tsv |
+    perl -nla -F"\t" -e '
+        my ( $name, $cov, $gc, @stat ) = @F;
+        printf qq(| %s | %s | %.1f-%.1f | %s | %s | %s |\n),
+            $name,
+            $cov ? sprintf( q(%d-%d), $cov, $cov * 2 ) : q(0-1),
+            $gc, $gc + 0.1,
+            @stat;
+    ' \
+    >> ../statMeta.md
+
+cat ../statMeta.md
+
+# statMeta.json
+anchr report --table ../statMeta.tsv
+
+log_info Done.
+
+exit 0
@@ -24,6 +24,19 @@ printf "| %s | %s | %s | %s | %s | %s | %s | %s | %s | %s | %s | %s | %s | %s |
 printf "|:--|--:|--:|--:|--:|--:|--:|--:|--:|--:|--:|--:|--:|--:|--:|--:|\n" \
     >> ${FILENAME_MD}
 
+# raw numbers, N50 and Sum in bp, runtimes in seconds
+FILENAME_TSV=${FILENAME_MD%.md}.tsv
+tsv_row \
+    "name" "cov_cor" "mapped_ratio" \
+    "n50_anchor_bp" "sum_anchor_bp" "count_anchor" \
+    "n50_others_bp" "sum_others_bp" "count_others" \
+    "median" "mad" "lower" "upper" \
+    "kmer" "runtime_ut_s" "runtime_an_s" \
+    > ${FILENAME_TSV}
+
+# rows are written inside directories of each part
+exec 3>> ${FILENAME_TSV}
+
 for X in {{ opt.cov }}; do
 	for P in $(printf "%03d " {0..{{ opt.statp }}}); do
 		if [ ! -e ${DIR_PREFIX}/MRX${X}P${P}/anchor/anchor.fasta ]; then
@@ -51,9 +64,29 @@ for X in {{ opt.cov }}; do
             $( time_format ${SECS_UT} ) \
             $( time_format ${SECS_AN} )
 
+		tsv_row \
+		    "MRX${X}P${P}" \
+		    "$( perl -e "print ${SUM_COR} / {{ opt.genome }};" )" \
+		    "${MAPPED_RATIO}" \
+		    "$( stat_raw anchor/anchor.fasta )" \
+		    "$( stat_raw anchor/pe.others.fa )" \
+		    "$( jq -r '.median // empty' anchor/env.json )" \
+		    "$( jq -r '.MAD // empty' anchor/env.json )" \
+		    "$( jq -r '.lower // empty' anchor/env.json )" \
+		    "$( jq -r '.upper // empty' anchor/env.json )" \
+		    "$( jq -r '.KMER // empty' env.json )" \
+		    "${SECS_UT}" \
+		    "${SECS_AN}" \
+		    >&3
+
 		popd > /dev/null
 	done
 done \
 >> ${FILENAME_MD}
 
+exec 3>&-
+
 cat ${FILENAME_MD}
+
+# JSON next to the tables
+anchr report --table ${FILENAME_TSV}
@@ -23,7 +23,20 @@ printf "| %s | %s | %s | %s | %s | %s | %s | %s | %s | %s | %s | %s | %s |\n" \
 printf "|:--|--:|--:|--:|--:|--:|--:|--:|--:|--:|--:|--:|--:|\n" \
     >> ${FILENAME_MD}
 
-for D in 8_spades 8_mr_spades 8_megahit 8_mr_megahit 8_platanus; do
+# raw numbers, N50 and Sum in bp, runtimes in seconds
+FILENAME_TSV=${FILENAME_MD%.md}.tsv
+tsv_row \
+    "name" "mapped_ratio" \
+    "n50_anchor_bp" "sum_anchor_bp" "count_anchor" \
+    "n50_others_bp" "sum_others_bp" "count_others" \
+    "median" "mad" "lower" "upper" \
+    "runtime_an_s" \
+    > ${FILENAME_TSV}
+
+# rows are written inside directories of each part
+exec 3>> ${FILENAME_TSV}
+
+for D in 8_spades 8_mr_spades 8_megahit 8_mr_megahit 8_platanus 8_flye 8_canu; do
 	if [ ! -e ${D}/anchor/anchor.fasta ]; then
 		continue;
 	fi
@@ -44,8 +57,25 @@ for D in 8_spades 8_mr_spades 8_megahit 8_mr_megahit 8_platanus; do
 		$( cat anchor/env.json | jq '.upper | tonumber' ) \
         $( time_format ${SECS_AN} )
 
+	tsv_row \
+	    "$(basename "${D}")" \
+	    "${MAPPED_RATIO}" \
+	    "$( stat_raw anchor/anchor.fasta )" \
+	    "$( stat_raw anchor/pe.others.fa )" \
+	    "$( jq -r '.median // empty' anchor/env.json )" \
+	    "$( jq -r '.MAD // empty' anchor/env.json )" \
+	    "$( jq -r '.lower // empty' anchor/env.json )" \
+	    "$( jq -r '.upper // empty' anchor/env.json )" \
+	    "${SECS_AN}" \
+	    >&3
+
 	popd > /dev/null
 done \
 >> ${FILENAME_MD}
 
+exec 3>&-
+
 cat ${FILENAME_MD}
+
+# JSON next to the tables
+anchr report --table ${FILENAME_TSV}
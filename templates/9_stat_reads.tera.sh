@@ -16,56 +16,83 @@ printf "| %s | %s | %s | %s |\n" \
     >> statReads.md
 printf "|:--|--:|--:|--:|\n" >> statReads.md
 
+# raw numbers, N50 and Sum in bp
+tsv_row "name" "n50_bp" "sum_bp" "count" > statReads.tsv
+
 if [ -e 1_genome/genome.fa ]; then
     printf "| %s | %s | %s | %s |\n" \
         $(echo "Genome";   faops n50 -H -S -C 1_genome/genome.fa;) >> statReads.md
+    tsv_row "Genome" "$( stat_raw 1_genome/genome.fa )" >> statReads.tsv
 fi
 if [ -e 1_genome/paralogs.fa ]; then
     printf "| %s | %s | %s | %s |\n" \
         $(echo "Paralogs"; faops n50 -H -S -C 1_genome/paralogs.fa;) >> statReads.md
+    tsv_row "Paralogs" "$( stat_raw 1_genome/paralogs.fa )" >> statReads.tsv
 fi
 if [ -e 1_genome/repetitives.fa ]; then
     printf "| %s | %s | %s | %s |\n" \
         $(echo "Repetitives"; faops n50 -H -S -C 1_genome/repetitives.fa;) >> statReads.md
+    tsv_row "Repetitives" "$( stat_raw 1_genome/repetitives.fa )" >> statReads.tsv
 fi
 
 for PREFIX in R S T; do
     if [ -e 2_illumina/${PREFIX}1.fq.gz ]; then
         printf "| %s | %s | %s | %s |\n" \
             $(echo "Illumina.${PREFIX}"; stat_format 2_illumina/${PREFIX}1.fq.gz {% if opt.se == "0" %}2_illumina/${PREFIX}2.fq.gz{% endif %};) >> statReads.md
+        tsv_row "Illumina.${PREFIX}" "$( stat_raw 2_illumina/${PREFIX}1.fq.gz {% if opt.se == "0" %}2_illumina/${PREFIX}2.fq.gz{% endif %} )" >> statReads.tsv
     fi
     if [ -e 2_illumina/trim/${PREFIX}1.fq.gz ]; then
         printf "| %s | %s | %s | %s |\n" \
             $(echo "trim.${PREFIX}"; stat_format 2_illumina/trim/${PREFIX}1.fq.gz {% if opt.se == "0" %}2_illumina/trim/${PREFIX}2.fq.gz 2_illumina/trim/${PREFIX}s.fq.gz{% endif %};) >> statReads.md
+        tsv_row "trim.${PREFIX}" "$( stat_raw 2_illumina/trim/${PREFIX}1.fq.gz {% if opt.se == "0" %}2_illumina/trim/${PREFIX}2.fq.gz 2_illumina/trim/${PREFIX}s.fq.gz{% endif %} )" >> statReads.tsv
     fi
 
-    parallel --no-run-if-empty -k -j 2 "
-        stat_format () {
-            echo \$(faops n50 -H -N 50 -S -C \$@) \
-                | perl -nla -MNumber::Format -e '
-                    printf qq(%d\t%s\t%d\n), \$F[0], Number::Format::format_bytes(\$F[1], base => 1000,), \$F[2];
-                '
-        }
-
-        if [ ! -e 2_illumina/Q{1}L{2}/${PREFIX}1.fq.gz ]; then
-            exit;
-        fi
+    # raw numbers of each combination, formatted for the Markdown table
+    ROWS=$(
+        parallel --no-run-if-empty -k -j 2 "
+            if [ ! -e 2_illumina/Q{1}L{2}/${PREFIX}1.fq.gz ]; then
+                exit;
+            fi
 
-        printf \"| %s | %s | %s | %s |\n\" \
-            \$(
-                echo Q{1}L{2};
+            printf \"%s\t%s\t%s\t%s\n\" \
+                \$(
+                    echo Q{1}L{2};
 {% if opt.se == "0" %}
-                stat_format \
-                    2_illumina/Q{1}L{2}/${PREFIX}1.fq.gz \
-                    2_illumina/Q{1}L{2}/${PREFIX}2.fq.gz \
-                    2_illumina/Q{1}L{2}/${PREFIX}s.fq.gz;
+                    faops n50 -H -N 50 -S -C \
+                        2_illumina/Q{1}L{2}/${PREFIX}1.fq.gz \
+                        2_illumina/Q{1}L{2}/${PREFIX}2.fq.gz \
+                        2_illumina/Q{1}L{2}/${PREFIX}s.fq.gz;
 {% else %}
-                stat_format \
-                    2_illumina/Q{1}L{2}/${PREFIX}1.fq.gz;
+                    faops n50 -H -N 50 -S -C \
+                        2_illumina/Q{1}L{2}/${PREFIX}1.fq.gz;
 {% endif %}
-            )
-        " ::: {{ opt.qual }} ::: {{ opt.len }} \
-        >> statReads.md
+                )
+            " ::: {{ opt.qual }} ::: {{ opt.len }}
+    )
+    if [ -n "${ROWS}" ]; then
+        echo "${ROWS}" >> statReads.tsv
+        echo "${ROWS}" |
+            perl -nla -F"\t" -MNumber::Format -e '
+                printf qq(| %s | %d | %s | %d |\n),
+                    $F[0], $F[1], Number::Format::format_bytes($F[2], base => 1000,), $F[3];
+            ' \
+            >> statReads.md
+    fi
 done
 
+{% if opt.long != "0" -%}
+{% if opt.ont != "0" %}{% set platform = "Nanopore" %}{% else %}{% set platform = "PacBio" %}{% endif -%}
+if [ -e 3_long/long.fasta ]; then
+    printf "| %s | %s | %s | %s |\n" \
+        $(echo "{{ platform }}"; stat_format {{ opt.long }};) >> statReads.md
+    printf "| %s | %s | %s | %s |\n" \
+        $(echo "{{ platform }}.filter"; stat_format 3_long/long.fasta;) >> statReads.md
+    tsv_row "{{ platform }}" "$( stat_raw {{ opt.long }} )" >> statReads.tsv
+    tsv_row "{{ platform }}.filter" "$( stat_raw 3_long/long.fasta )" >> statReads.tsv
+fi
+
+{% endif -%}
 cat statReads.md
+
+# statReads.json
+anchr report --table statReads.tsv
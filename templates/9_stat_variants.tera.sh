@@ -0,0 +1,86 @@
+{%- include "header" -%}
+{# Keep a blank line #}
+#----------------------------#
+# Run
+#----------------------------#
+log_warn 9_stat_variants.sh
+
+#----------------------------#
+# set parameters
+#----------------------------#
+USAGE="Usage: $0 [FILENAME_MD]"
+
+FILENAME_MD=${1:-"statVariants.md"}
+
+# numbers of variants, af_* are bins of allele frequencies in percent
+FILENAME_TSV=${FILENAME_MD%.md}.tsv
+tsv_row \
+    "name" "variants" "snps" "indels" \
+    "af_0_10" "af_10_30" "af_30_70" "af_70_90" "af_90_100" \
+    > ${FILENAME_TSV}
+
+# Only passed sites are counted
+# AF comes from the FORMAT field AF (Mutect2), or AO / (AO + RO) (freebayes)
+for D in 3_gatk 3_freebayes; do
+    if [ ! -e ${D}/R.filtered.vcf ]; then
+        continue;
+    fi
+
+    cat ${D}/R.filtered.vcf |
+        NAME=$(echo ${D} | sed 's/^3_//') perl -nla -F'\t' -e '
+            next if /^#/;
+            next unless $F[6] eq q(PASS) or $F[6] eq q(.);
+
+            my ($ref, $alt) = ($F[3], (split /,/, $F[4])[0]);
+            $variants++;
+            if (length($ref) == 1 and length($alt) == 1) {
+                $snps++;
+            }
+            elsif (length($ref) != length($alt)) {
+                $indels++;
+            }
+
+            my @keys = split /:/, $F[8];
+            my @values = split /:/, $F[9];
+            my %fmt = map { $keys[$_] => $values[$_] } 0 .. $#keys;
+
+            my $af;
+            if (defined $fmt{AF}) {
+                $af = (split /,/, $fmt{AF})[0];
+            }
+            elsif (defined $fmt{AO} and defined $fmt{RO}) {
+                my $ao = (split /,/, $fmt{AO})[0];
+                $af = $ao + $fmt{RO} > 0 ? $ao / ($ao + $fmt{RO}) : 0;
+            }
+            next unless defined $af and $af ne q(.);
+
+            if    ($af < 0.1) { $bins[0]++ }
+            elsif ($af < 0.3) { $bins[1]++ }
+            elsif ($af < 0.7) { $bins[2]++ }
+            elsif ($af < 0.9) { $bins[3]++ }
+            else              { $bins[4]++ }
+
+            END {
+                printf qq(%s\t%d\t%d\t%d\t%d\t%d\t%d\t%d\t%d\n),
+                    $ENV{NAME}, $variants, $snps, $indels,
+                    map { $bins[$_] || 0 } 0 .. 4;
+            }
+        '
+done \
+    >> ${FILENAME_TSV}
+
+echo -e "Table: ${FILENAME_MD}\n" > ${FILENAME_MD}
+printf "| %s | %s | %s | %s | %s | %s | %s | %s | %s |\n" \
+    "Name" "Variants" "SNPs" "Indels" \
+    "AF<0.1" "0.1-0.3" "0.3-0.7" "0.7-0.9" "AF>=0.9" \
+    >> ${FILENAME_MD}
+printf "|:--|--:|--:|--:|--:|--:|--:|--:|--:|\n" \
+    >> ${FILENAME_MD}
+sed 1d ${FILENAME_TSV} |
+    perl -nla -F"\t" -e 'print q(| ) . join(q( | ), @F) . q( |)' \
+    >> ${FILENAME_MD}
+
+cat ${FILENAME_MD}
+
+# JSON next to the tables
+anchr report --table ${FILENAME_TSV}
@@ -82,6 +82,14 @@ cat basecov.txt |
         Path::Tiny::path(q(env.json))->spew( JSON->new->encode($json) );
     '
 
+# histogram of base coverages, used by `anchr report --html`
+#Coverage	Count
+cat basecov.txt |
+    grep -v '^#' |
+    tsv-summarize --group-by 3 --count |
+    sort -n \
+    > basecov.hist.tsv
+
 #----------------------------#
 # Properly covered regions by reads
 #----------------------------#
@@ -195,6 +203,16 @@ cat basecov.txt |
         }
     ' \
     > contig.covered.txt
+
+{% if opt.organelle == "1" -%}
+# mean coverage of each contig
+log_debug "contig coverage"
+cat basecov.txt |
+    grep -v '^#' |
+    tsv-summarize --group-by 1 --mean 3 \
+    > contig.cov.tsv
+
+{% endif -%}
 find . -type f -name "basecov.txt" | parallel --no-run-if-empty -j 1 rm
 
 #----------------------------#
@@ -323,6 +341,78 @@ log_info "pe.anchor.fa & pe.others.fa"
 faops region -l 0 SR.fasta anchor.regions.txt pe.anchor.fa
 faops region -l 0 SR.fasta others.regions.txt pe.others.fa
 
+{% if opt.organelle == "1" -%}
+#----------------------------#
+# Organelles and plasmids
+#----------------------------#
+log_info "organelle.fasta"
+
+# High-copy contigs in the others set, written in full length
+# Circular ones have the same sequences at both ends
+perl -MPath::Tiny -MJSON -e '
+    my $json = JSON->new->decode( Path::Tiny::path( q(env.json) )->slurp );
+    my $median = $json->{median};
+
+    my %in_others = map { ( split /:/ )[0] => 1 }
+        Path::Tiny::path( q(others.regions.txt) )->lines( { chomp => 1 } );
+    my %cov_of = map { split /\t/ }
+        Path::Tiny::path( q(contig.cov.tsv) )->lines( { chomp => 1 } );
+
+    my ( @names, %seq_of );
+    for ( Path::Tiny::path( q(SR.fasta) )->lines( { chomp => 1 } ) ) {
+        if (/^>(\S+)/) {
+            push @names, $1;
+            $seq_of{$1} = q();
+        }
+        else {
+            $seq_of{ $names[-1] } .= $_;
+        }
+    }
+
+    open my $fa_fh,  q(>), q(organelle.fasta);
+    open my $tsv_fh, q(>), q(organelle.tsv);
+    print {$tsv_fh} join( qq(\t), qw(#name length coverage ratio gc overlap) ), qq(\n);
+
+    for my $name (@names) {
+        next unless $in_others{$name};
+        next unless $median > 0 and defined $cov_of{$name};
+
+        my $seq = $seq_of{$name};
+        my $length = length $seq;
+        next if $length < {{ opt.min }};
+
+        my $ratio = $cov_of{$name} / $median;
+        next if $ratio < {{ opt.orgratio }};
+
+        my $acgt = ( $seq =~ tr/ACGTacgt// );
+        my $gc = $acgt ? ( $seq =~ tr/GCgc// ) / $acgt : 0;
+{%- if opt.gc_max != "0" %}
+        next if $gc < {{ opt.gc_min }} or $gc > {{ opt.gc_max }};
+{%- endif %}
+
+        # the longest suffix equal to the prefix
+        my $overlap = 0;
+        my $seed = substr $seq, 0, 31;
+        my $pos = index $seq, $seed, int( $length / 2 );
+        while ( $pos > 0 ) {
+            if ( substr( $seq, $pos ) eq substr( $seq, 0, $length - $pos ) ) {
+                $overlap = $length - $pos;
+                last;
+            }
+            $pos = index $seq, $seed, $pos + 1;
+        }
+
+        print {$fa_fh} qq(>$name\n$seq\n);
+        printf {$tsv_fh} qq(%s\t%d\t%.1f\t%.2f\t%.4f\t%d\n),
+            $name, $length, $cov_of{$name}, $ratio, $gc, $overlap;
+    }
+    '
+
+ORGANELLE_COUNT=$( faops n50 -H -N 0 -C organelle.fasta )
+save ORGANELLE_COUNT
+
+{% endif -%}
+
 #----------------------------#
 # Merging anchors
 #----------------------------#
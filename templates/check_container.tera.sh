@@ -0,0 +1,32 @@
+#!/usr/bin/env bash
+
+# Check images of external dependencies
+
+hash {{ opt.container }} 2>/dev/null || {
+    echo >&2 "{{ opt.container }} is required but it's not installed.";
+    exit 1;
+}
+
+CONTAINER={{ opt.container }}
+MISSING=0
+
+check_image () {
+    if [ "${CONTAINER}" == "docker" ]; then
+        docker image inspect "$2" > /dev/null 2>&1
+    else
+        # local image files; URIs like docker://... will be pulled at runtime
+        [ -e "$2" ]
+    fi || {
+        echo >&2 "$1: image $2 does not exist locally";
+        MISSING=$((MISSING + 1))
+    }
+}
+
+{% for tool, image in images -%}
+check_image {{ tool }} "{{ image }}"
+{% endfor -%}
+{# Keep a blank line #}
+if [ "${MISSING}" -gt 0 ]; then
+    echo >&2 "${MISSING} image(s) are missing";
+    exit 1;
+fi
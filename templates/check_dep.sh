@@ -23,6 +23,12 @@ hash jq 2>/dev/null || {
     exit 1;
 }
 
+hash flock 2>/dev/null || {
+    echo >&2 "flock is required but it's not installed.";
+    echo >&2 "Install with homebrew: brew install flock";
+    exit 1;
+}
+
 hash pigz 2>/dev/null || {
     echo >&2 "pigz is required but it's not installed.";
     echo >&2 "Install with homebrew: brew install pigz";
@@ -66,12 +72,6 @@ hash bbduk.sh 2>/dev/null || {
     exit 1;
 }
 
-hash sickle 2>/dev/null || {
-    echo >&2 "sickle is required but it's not installed.";
-    echo >&2 "Install with homebrew: brew install sickle";
-    exit 1;
-}
-
 hash tsv-sample 2>/dev/null || {
     echo >&2 "tsv-sample is required but it's not installed.";
     echo >&2 "Install with homebrew: brew install wang-q/tap/tsv-utils";
@@ -39,6 +39,20 @@ if [[ "$OSTYPE" != "darwin"* ]]; then
     ulimit -s unlimited
 fi
 
+# run a command, retry on failure
+# ANCHR_RETRIES=2 retry bash 8_spades.sh
+retry () {
+    local N=0
+    until "$@"; do
+        N=$((N + 1))
+        if [ "${N}" -gt "${ANCHR_RETRIES:-0}" ]; then
+            log_warn "Failed after ${N} attempt(s): $*"
+            return 1
+        fi
+        log_warn "Retry ${N}/${ANCHR_RETRIES}: $*"
+    done
+}
+
 signaled () {
     log_warn Interrupted
     exit 1
@@ -70,6 +84,23 @@ stat_format () {
         '
 }
 
+# N50, sum in bp and count of sequences for TSV tables, empty fields for missing files
+stat_raw () {
+    local STAT
+    STAT=$( faops n50 -H -N 50 -S -C "$@" 2> /dev/null ) || true
+    if [ -z "${STAT}" ]; then
+        printf "\t\t\n"
+    else
+        echo ${STAT} | tr " " "\t"
+    fi
+}
+
+# join arguments into a TSV row, empty ones are kept
+tsv_row () {
+    local IFS=$'\t'
+    echo "$*"
+}
+
 time_format () {
     echo $@ |
         perl -nl -e '
@@ -84,3 +115,145 @@ time_format () {
 readlinkf () {
     perl -MCwd -l -e 'print Cwd::abs_path shift' "$1";
 }
+
+{% if opt.container and opt.container != "0" -%}
+#----------------------------#
+# container
+#----------------------------#
+# the project directory and ${ANCHR_BIND} (space separated) are mounted
+export ANCHR_PROJECT=${ANCHR_PROJECT:-${BASH_DIR}}
+export ANCHR_CONTAINER={{ opt.container }}
+
+container_exec () {
+    IMAGE=$1
+    shift
+
+    if [ "${ANCHR_CONTAINER}" == "docker" ]; then
+        BINDS="-v ${ANCHR_PROJECT}:${ANCHR_PROJECT}"
+        for D in ${ANCHR_BIND:-}; do
+            BINDS="${BINDS} -v ${D}:${D}"
+        done
+        docker run --rm -i -u "$(id -u):$(id -g)" ${BINDS} -w "${PWD}" "${IMAGE}" "$@"
+    else
+        BINDS="${ANCHR_PROJECT}"
+        for D in ${ANCHR_BIND:-}; do
+            BINDS="${BINDS},${D}"
+        done
+        ${ANCHR_CONTAINER} exec --bind "${BINDS}" --pwd "${PWD}" "${IMAGE}" "$@"
+    fi
+}
+export -f container_exec
+
+{% for tool, image in images -%}
+{{ tool }} () { container_exec "{{ image }}" {{ tool }} "$@"; }
+export -f {{ tool }}
+{% endfor -%}
+{# Keep a blank line #}
+{% endif -%}
+#----------------------------#
+# provenance
+#----------------------------#
+# provenance.json is created by `anchr template`; nested scripts inherit its path
+if [ -z "${ANCHR_PROVENANCE:-}" ] && [ -e "${BASH_DIR}/provenance.json" ]; then
+    export ANCHR_PROVENANCE="${BASH_DIR}/provenance.json"
+fi
+
+# apply a jq filter to provenance.json
+# stages run in parallel by 0_bsub.sh or `parallel`, so updates are serialized by a lock
+update_provenance () {
+    (
+        flock 9
+        TMP="${ANCHR_PROVENANCE}.$$"
+        jq "$@" "${ANCHR_PROVENANCE}" > "${TMP}" &&
+            mv "${TMP}" "${ANCHR_PROVENANCE}"
+    ) 9> "${ANCHR_PROVENANCE}.lock"
+}
+
+# save the version of an external tool
+# save_version spades.py
+# save_version bwa "bwa 2>&1 | grep Version"
+save_version () {
+    if [ -z "${ANCHR_PROVENANCE:-}" ]; then
+        return;
+    fi
+
+    if [ "$#" -gt 1 ]; then
+        VERSION=$( eval "$2" 2>&1 | grep -v '^\s*$' | head -n 1 ) || true
+    else
+        VERSION=$( $1 --version 2>&1 | grep -v '^\s*$' | head -n 1 ) || true
+    fi
+
+    update_provenance --sort-keys --arg tool "$1" --arg version "${VERSION}" \
+        '.tools[$tool] = $version'
+}
+
+# save the command line of this script
+save_stage () {
+    if [ -z "${ANCHR_PROVENANCE:-}" ]; then
+        return;
+    fi
+
+    update_provenance --arg script "$(basename "$0")" --arg dir "${BASH_DIR}" \
+        --arg args "$*" --arg start "$(date -u +%Y-%m-%dT%H:%M:%SZ)" \
+        '.stages += [{script: $script, dir: $dir, args: $args, start: $start}]'
+}
+
+#----------------------------#
+# resources
+#----------------------------#
+# re-run this script under GNU time, once per script
+# wall time, CPU time and peak RSS go to resources/ next to provenance.json
+ANCHR_SCRIPT="${BASH_DIR}/$(basename "${BASH_SOURCE[0]}")"
+if [ -n "${ANCHR_PROVENANCE:-}" ] &&
+    [ "${ANCHR_TIMED:-}" != "${ANCHR_SCRIPT}" ] &&
+    [ -f "${ANCHR_SCRIPT}" ] &&
+    /usr/bin/time -v true > /dev/null 2>&1; then
+    # scripts of down-sampling parts are kept in their own sub-directories
+    PROJECT_DIR=$(dirname "${ANCHR_PROVENANCE}")
+    RESOURCE_DIR="${PROJECT_DIR}/resources${BASH_DIR#${PROJECT_DIR}}"
+    RESOURCE_NAME=$( echo "$(basename "${ANCHR_SCRIPT}") $*" | sed 's/ *$//; s/[^A-Za-z0-9._-]/_/g' )
+    mkdir -p "${RESOURCE_DIR}"
+
+    ANCHR_TIMED="${ANCHR_SCRIPT}" /usr/bin/time -v -o "${RESOURCE_DIR}/${RESOURCE_NAME}.txt" \
+        bash "${ANCHR_SCRIPT}" "$@"
+    exit $?
+fi
+
+save_stage "$@"
+{% if opt.retries and opt.retries != "0" -%}
+export ANCHR_RETRIES=${ANCHR_RETRIES:-{{ opt.retries }}}
+{% endif -%}
+{% if opt.genome_auto and opt.genome_auto == "1" -%}
+{# Keep a blank line #}
+#----------------------------#
+# genome size
+#----------------------------#
+# estimated from the k-mer histogram by 2_kmer_profile.sh
+if [ -e "${BASH_DIR}/2_illumina/kmer/env.json" ]; then
+    GENOME_SIZE=$( jq '.GENOME_SIZE | tonumber' "${BASH_DIR}/2_illumina/kmer/env.json" )
+fi
+{% if genome_required -%}
+if [ -z "$GENOME_SIZE" ]; then
+    log_warn "--genome auto: no GENOME_SIZE in 2_illumina/kmer/env.json, run 2_kmer_profile.sh first"
+    exit 1
+fi
+{% endif -%}
+{% endif -%}
+{% if opt.strict and opt.strict == "1" -%}
+{# Keep a blank line #}
+#----------------------------#
+# strict mode
+#----------------------------#
+# stop at the first error, leave a marker of the failed stage
+failed () {
+    local RC=$1
+    log_warn "$(basename "$0") failed at line $2: $3"
+    printf "%s\t%s\t%s\n" "$2" "${RC}" "$3" > "${BASH_DIR}/$(basename "$0").failed"
+    exit 1
+}
+
+rm -f "${BASH_DIR}/$(basename "$0").failed"
+set -euo pipefail
+set -o errtrace
+trap 'failed $? ${LINENO} "${BASH_COMMAND}"' ERR
+{% endif -%}
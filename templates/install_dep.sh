@@ -12,11 +12,11 @@ check_install () {
     fi
 }
 
-for package in openjdk jq parallel pigz; do
+for package in openjdk jq flock parallel pigz; do
     check_install ${package}
 done
 
-for package in fastqc sickle bwa samtools picard-tools; do
+for package in fastqc bwa samtools picard-tools; do
     check_install ${package}
 done
 
@@ -114,13 +114,11 @@ bbduk.sh \
     qtrim=r trimq={{ opt.qual }} minlen={{ opt.len }} overwrite
 
 # Separates unmerged reads
-repair.sh \
-    in=unmerged.trim.fq.gz \
-    out={{ opt.prefixu }}1.fq.gz \
-    out2={{ opt.prefixu }}2.fq.gz \
-    outs={{ opt.prefixu }}s.fq.gz \
-    threads={{ opt.parallel }}{% if opt.xmx != "0" %} -Xmx{{ opt.xmx }}{% endif %} \
-    repair overwrite
+anchr reads repair \
+    unmerged.trim.fq.gz \
+    --out1 {{ opt.prefixu }}1.fq.gz \
+    --out2 {{ opt.prefixu }}2.fq.gz \
+    --outs {{ opt.prefixu }}s.fq.gz
 
 #----------------------------#
 # Done.
@@ -136,7 +136,7 @@ fi
 # -a Minimum count for an anchor k-mer (3)
 # -w Size of window (10)
 # -e Maximum number of error in a window (3)
-# As we have trimmed reads with sickle, we lower `-e` to 1 from original value of 3,
+# As we have trimmed reads with `anchr qtrim`, we lower `-e` to 1 from original value of 3,
 # remove `--no-discard`.
 # And we only want most reliable parts of the genome other than the whole genome, so dropping rare
 # k-mers is totally OK for us. Raise `-m` from 1 to 3, `-g` from 1 to 3, and `-a` from 1 to 4.
@@ -107,6 +107,109 @@ if [ ! -e filter.fq.gz ]; then
 fi
 rm temp.fq.gz; ln -s filter.fq.gz temp.fq.gz
 
+{% if opt.screen != "0" -%}
+# Classify reads against a local database, no network access
+log_info "screen with {{ opt.screener }}"
+if [ ! -e {{ opt.prefix }}.screen.tsv ]; then
+{%- if opt.screener == "kraken2" %}
+    kraken2 \
+        --db {{ opt.screen }} \
+        --threads {{ opt.parallel }} \
+        --report {{ opt.prefix }}.screen.report.txt \
+        --output screen.kraken.txt \
+        temp.fq.gz
+
+    # Species and unclassified, percentages of reads
+    cat {{ opt.prefix }}.screen.report.txt |
+        perl -nla -F"\t" -e '
+            $F[3] =~ /^[SU]$/ or next;
+            $F[0] >= 0.1 or next;
+            $F[5] =~ s/^\s+//;
+            print join qq(\t), $F[5], $F[4], $F[0];
+        ' |
+        sort -t$'\t' -k3,3gr \
+        > {{ opt.prefix }}.screen.tsv
+{%- else %}
+    mash screen -w -p {{ opt.parallel }} \
+        {{ opt.screen }} \
+        temp.fq.gz \
+        > {{ opt.prefix }}.screen.mash.tab
+
+    # identity, shared-hashes, median-multiplicity, p-value, query-ID, query-comment
+    cat {{ opt.prefix }}.screen.mash.tab |
+        perl -nla -F"\t" -e '
+            $F[0] >= 0.9 or next;
+            my $name = length $F[5] ? $F[5] : $F[4];
+            print join qq(\t), $name, $F[1], $F[0] * 100;
+        ' |
+        sort -t$'\t' -k3,3gr \
+        > {{ opt.prefix }}.screen.tsv
+{%- endif %}
+fi
+
+{% if opt.taxon != "0" -%}
+# Remove reads assigned outside the target taxon
+log_info "remove reads outside {{ opt.taxon }}"
+if [ ! -e screen.fq.gz ]; then
+    # The report is in the depth-first order of the taxonomy, indented by depth
+    # Taxa in the subtree of the target and in its lineage are kept
+    cat {{ opt.prefix }}.screen.report.txt |
+        perl -nla -F"\t" -e '
+            BEGIN { our ( @lineage, %keep, $depth ); }
+
+            my ($indent) = $F[5] =~ /^(\s*)/;
+            my $d = length $indent;
+            ( my $name = $F[5] ) =~ s/^\s+//;
+
+            if ( defined $depth ) {
+                if ( $d > $depth ) {
+                    $keep{ $F[4] }++;
+                    next;
+                }
+                undef $depth;
+            }
+
+            splice @lineage, $d;
+            $lineage[$d] = $F[4];
+
+            if ( $F[4] eq q({{ opt.taxon }}) or $name eq q({{ opt.taxon }}) ) {
+                $keep{$_}++ for grep {defined} @lineage;
+                $depth = $d;
+            }
+
+            END {
+                print for sort { $a <=> $b } keys %keep;
+            }
+        ' \
+        > screen.keep.txt
+
+    if [ ! -s screen.keep.txt ]; then
+        log_warn "Can't find {{ opt.taxon }} in {{ opt.prefix }}.screen.report.txt"
+        exit 1
+    fi
+
+    cat screen.kraken.txt |
+        perl -nla -F"\t" -e '
+            BEGIN {
+                open my $fh, q{<}, q{screen.keep.txt};
+                our %keep = map { chomp; $_ => 1 } <$fh>;
+            }
+            $F[0] eq q{C} or next;
+            $keep{ $F[2] } and next;
+            print $F[1];
+        ' \
+        > screen.remove.txt
+
+    filterbyname.sh \
+        in=temp.fq.gz \
+        out=screen.fq.gz \
+        names=screen.remove.txt include=f tws=t{% if args.1 %} int=t{% endif %} \
+        threads={{ opt.parallel }}{% if opt.xmx != "0" %} -Xmx{{ opt.xmx }}{% endif %}
+fi
+rm temp.fq.gz; ln -s screen.fq.gz temp.fq.gz
+
+{% endif -%}
+{% endif -%}
 log_info "kmer histogram and peaks"
 if [ ! -e peaks.final.txt ]; then
     kmercountexact.sh \
@@ -116,63 +219,29 @@ if [ ! -e peaks.final.txt ]; then
 fi
 
 # Revert to normal pair-end fastq files
-log_info "re-pair with repair.sh"
+log_info "re-pair with anchr reads repair"
 if [ ! -e {{ opt.prefix }}1.trim.fq.gz ]; then
 {% if args.1 -%}
-    repair.sh \
-        in=temp.fq.gz \
-        out={{ opt.prefix }}1.fq.gz \
-        out2={{ opt.prefix }}2.fq.gz \
-        outs={{ opt.prefix }}s.fq.gz \
-        repair \
-        threads={{ opt.parallel }}{% if opt.xmx != "0" %} -Xmx{{ opt.xmx }}{% endif %}
+    anchr reads repair \
+        temp.fq.gz \
+        --out1 {{ opt.prefix }}1.fq.gz \
+        --out2 {{ opt.prefix }}2.fq.gz \
+        --outs {{ opt.prefix }}s.fq.gz
 {% else -%}
     cp -L temp.fq.gz {{ opt.prefix }}1.fq.gz
 {% endif -%}
 fi
 
 #----------------------------#
-# Sickle
+# Quality and length
 #----------------------------#
-log_info "sickle ::: Qual {{ opt.qual }} ::: Len {{ opt.len }}"
-parallel --no-run-if-empty --linebuffer -k -j 2 "
-    mkdir -p Q{1}L{2}
-    cd Q{1}L{2}
-
-    printf '==> Qual-Len: %s\n'  Q{1}L{2}
-    if [ -e {{ opt.prefix }}1.fq.gz ]; then
-        echo '    {{ opt.prefix }}1.fq.gz already presents'
-        exit;
-    fi
-
-{% if args.1 -%}
-    sickle pe \
-        -t sanger \
-        -q {1} \
-        -l {2} \
-        -f ../{{ opt.prefix }}1.fq.gz \
-        -r ../{{ opt.prefix }}2.fq.gz \
-        -o {{ opt.prefix }}1.fq \
-        -p {{ opt.prefix }}2.fq \
-        -s {{ opt.prefix }}s.fq
-    sickle se \
-        -t sanger \
-        -q {1} \
-        -l {2} \
-        -f ../{{ opt.prefix }}s.fq.gz \
-        -o {{ opt.prefix }}s.temp.fq
-    cat {{ opt.prefix }}s.temp.fq >> {{ opt.prefix }}s.fq
-    rm {{ opt.prefix }}s.temp.fq
-{% else -%}
-    sickle se \
-        -t sanger \
-        -q {1} \
-        -l {2} \
-        -f ../{{ opt.prefix }}1.fq.gz \
-        -o {{ opt.prefix }}1.fq
-{% endif -%}
-
-    pigz *.fq
-    " ::: {{ opt.qual }} ::: {{ opt.len }}
-
+log_info "qtrim ::: Qual {{ opt.qual }} ::: Len {{ opt.len }}"
+anchr qtrim \
+    --qual "{{ opt.qual }}" \
+    --len "{{ opt.len }}" \
+    --prefix {{ opt.prefix }} \
+{% if args.1 %}    --single {{ opt.prefix }}s.fq.gz \
+    {{ opt.prefix }}1.fq.gz {{ opt.prefix }}2.fq.gz
+{% else %}    {{ opt.prefix }}1.fq.gz
+{% endif %}
 exit 0
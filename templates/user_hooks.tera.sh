@@ -0,0 +1,25 @@
+{#- User stages running after built-in ones, see `anchr template --templates` -#}
+{#- stages: built-in stage => user scripts, in the order of creation -#}
+{#- Called as `{{- ... }}` right after a block; each script starts on a new line -#}
+{% macro master(after, stages) -%}
+{% for name in after -%}
+{% for s in stages[name] %}
+if [ -e {{ s }} ]; then
+    retry bash {{ s }};
+fi
+{%- endfor -%}
+{% endfor -%}
+{% endmacro master %}
+
+{#- Each job waits for `wait` and the previous user stage -#}
+{% macro bsub(after, wait, stages, queue, parallel) -%}
+{% set_global prev = "" -%}
+{% for name in after -%}
+{% for s in stages[name] %}
+bsub -w "{{ wait }}{% if prev != "" %} && ended(${BASE_NAME}-{{ prev }}){% endif %}" \
+    -q {{ queue }} -n {{ parallel }} -J "${BASE_NAME}-{{ s | trim_end_matches(pat=".sh") }}" \
+    "bash {{ s }}"
+{%- set_global prev = s | trim_end_matches(pat=".sh") -%}
+{% endfor -%}
+{% endfor -%}
+{% endmacro bsub %}
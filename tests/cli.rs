@@ -1,6 +1,7 @@
 use assert_cmd::prelude::*; // Add methods on commands
 use predicates::prelude::*; // Used for writing assertions
 use std::env;
+use std::io::Read;
 use std::process::Command;
 use tempfile::TempDir; // Run programs
 
@@ -108,11 +109,45 @@ fn command_trim() -> Result<(), Box<dyn std::error::Error>> {
     let stdout = String::from_utf8(output.stdout).unwrap();
 
     assert!(stdout.lines().count() > 40);
-    assert!(stdout.contains("Sickle"));
+    assert!(stdout.contains("anchr qtrim"));
+    assert!(stdout.contains("--single Rs.fq.gz"));
 
     assert!(&tempdir.path().join("illumina_adapters.fa").is_file());
     assert!(&tempdir.path().join("sequencing_artifacts.fa").is_file());
 
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("trim")
+        .arg("tests/Lambda/R1.fq.gz")
+        .arg("tests/Lambda/R2.fq.gz")
+        .arg("--screen")
+        .arg("k2_db")
+        .arg("--taxon")
+        .arg("562")
+        .arg("-o")
+        .arg("stdout")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.contains("kraken2"));
+    assert!(stdout.contains("filterbyname.sh"));
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("trim")
+        .arg("tests/Lambda/R1.fq.gz")
+        .arg("--screen")
+        .arg("refseq.msh")
+        .arg("--screener")
+        .arg("mash")
+        .arg("--taxon")
+        .arg("562")
+        .arg("-o")
+        .arg("stdout");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--taxon needs --screener kraken2"));
+
     assert!(env::set_current_dir(&curdir).is_ok());
     assert!(tempdir.close().is_ok());
 
@@ -189,6 +224,730 @@ fn command_anchors() -> Result<(), Box<dyn std::error::Error>> {
 
     assert!(stdout.lines().count() > 50);
     assert!(stdout.contains("bbwrap.sh"));
+    assert!(!stdout.contains("organelle.fasta"));
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("anchors")
+        .arg("tests/Lambda/unitigs.fasta")
+        .arg("tests/Lambda/R1.fq.gz")
+        .arg("--organelle")
+        .arg("--gc")
+        .arg("0.2-0.45")
+        .arg("-o")
+        .arg("stdout")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.contains("organelle.fasta"));
+    assert!(stdout.contains("$gc > 0.45"));
+
+    Ok(())
+}
+
+#[test]
+fn command_report_resources() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("report")
+        .arg("tests/Lambda")
+        .arg("--resources")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(stdout.lines().count(), 14);
+    assert!(stdout.contains("| 4_anchors.sh 4_unitigs_bcalm | . | 0:01:07 |"));
+    assert!(stdout.contains("4_unitigs_bcalm/Q0L0X40P001/anchor"));
+    assert!(stdout.contains("| anchors.sh | 2 | 0:00:32 |"));
+    assert!(stdout.contains("| 1:04:22 |"));
+
+    // only --merge takes many directories
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("report")
+        .arg("tests/Lambda")
+        .arg("tests/Lambda")
+        .arg("--resources")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Only --merge takes more than one directory"));
+
+    Ok(())
+}
+
+#[test]
+fn command_report_html() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("report")
+        .arg("tests/Lambda")
+        .arg("--html")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.starts_with("<!DOCTYPE html>"));
+    assert!(stdout.contains("<h2>Assemblies</h2>"));
+    assert!(stdout.contains("<h3>statFinal</h3>"));
+    assert!(stdout.contains("<td style=\"text-align:right\">48502</td>"));
+    assert!(stdout.contains("Base coverages of 4_unitigs_bcalm/Q0L0X40P000/anchor"));
+    assert!(stdout.contains(">lower 20</text>"));
+    assert!(stdout.contains(">upper 82</text>"));
+    assert!(stdout.trim_end().ends_with("</html>"));
+
+    Ok(())
+}
+
+#[test]
+fn command_report_table() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = TempDir::new().unwrap();
+    let tsv = tempdir.path().join("statFinal.tsv");
+    std::fs::write(
+        &tsv,
+        std::fs::read_to_string("tests/Lambda/statFinal.tsv")? + "polish\t\t\t\n",
+    )?;
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("report")
+        .arg("--table")
+        .arg(tsv.to_str().unwrap())
+        .assert()
+        .success();
+
+    let json = std::fs::read_to_string(tempdir.path().join("statFinal.json"))?;
+    assert!(json.contains("\"table\": \"statFinal\""));
+    assert!(json.contains("\"n50_bp\": 28411"));
+    assert!(json.contains("\"name\": \"spades.non-contained\""));
+    assert!(json.contains("\"sum_bp\": null"));
+
+    // Markdown tables are not parsed
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("report")
+        .arg("--table")
+        .arg("tests/Lambda/statFinal.md")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not a TSV table"));
+
+    Ok(())
+}
+
+#[test]
+fn command_report_merge() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("report")
+        .arg("tests/Lambda")
+        .arg("tests/Lambda")
+        .arg("--merge")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(stdout.lines().count(), 19);
+    assert!(stdout.contains("Lambda\tstatFinal\tGenome\tn50_bp\t48502"));
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("report")
+        .arg("tests/Lambda")
+        .arg("tests/Lambda")
+        .arg("--merge")
+        .arg("--stat")
+        .arg("statFinal")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(stdout.lines().count(), 7);
+    assert!(stdout.starts_with("project\tname\tn50_bp\tsum_bp\tcount\n"));
+
+    Ok(())
+}
+
+#[test]
+fn command_compare() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("compare")
+        .arg("tests/Lambda")
+        .arg("tests/Lambda2")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.contains(
+        "| statFinal | 7_merge_anchors.anchors | n50_bp | 28411 | 30230 | +1819 | +6.40% |"
+    ));
+    assert!(stdout.contains("| 2_trim.sh | 0:00:33 | 0:02:30 | +0:01:57 |"));
+    assert!(stdout.contains("| A | 3 | 2300 | 1 | 1000 | 1 | 800 | 1 | 500 |"));
+    assert!(stdout.contains("| B | 3 | 2400 | 1 | 1000 | 1 | 800 | 1 | 600 |"));
+    assert!(stdout.contains("| A | anchor_2 | 800 |"));
+
+    Ok(())
+}
+
+#[test]
+fn command_kmer_profile() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("kmer-profile")
+        .arg("tests/Lambda/R.khist.txt")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    let env: serde_json::Value = serde_json::from_str(&stdout)?;
+    let genome = env["GENOME_SIZE"].as_u64().unwrap();
+    assert!(genome > 45000 && genome < 52000);
+    assert!(env["HETEROZYGOSITY"].as_f64().unwrap() < 0.001);
+    assert_eq!(env["ERROR_THRESHOLD"].as_u64().unwrap(), 12);
+    assert_eq!(env["KMER"].as_u64().unwrap(), 31);
+
+    // non-finite counts are skipped
+    let tempdir = TempDir::new().unwrap();
+    let khist = tempdir.path().join("khist.txt");
+    let mut content = std::fs::read_to_string("tests/Lambda/R.khist.txt")?;
+    content += "36\tnan\tNaN\n";
+    std::fs::write(&khist, content)?;
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd.arg("kmer-profile").arg(&khist).output().unwrap();
+    assert!(output.status.success());
+    let env: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(env["ERROR_THRESHOLD"].as_u64().unwrap(), 12);
+
+    Ok(())
+}
+
+#[test]
+fn command_select() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd.arg("select").arg("tests/Lambda").output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(stdout.lines().count(), 9);
+    assert!(stdout.contains("Recommended: --merge --cov \"40\""));
+    assert!(stdout.contains("| 2 | bcalm Q0L0X40 | 2 | 1450 |"));
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("select")
+        .arg("tests/Lambda")
+        .arg("--weights")
+        .arg("sum=1")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.contains("Recommended: --qual \"0\" --len \"0\" --cov \"40\""));
+
+    for weights in &["n50=nan", "sum=inf", "count=-inf"] {
+        let mut cmd = Command::cargo_bin("anchr")?;
+        let output = cmd
+            .arg("select")
+            .arg("tests/Lambda")
+            .arg("--weights")
+            .arg(weights)
+            .output()
+            .unwrap();
+        let stderr = String::from_utf8(output.stderr).unwrap();
+
+        assert!(!output.status.success());
+        assert!(stderr.contains(&format!("Invalid weight {}", weights)));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn command_dep_container() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("dep")
+        .arg("check")
+        .arg("--container")
+        .arg("docker")
+        .arg("--images")
+        .arg("tests/Lambda/images.tsv")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.contains("docker image inspect"));
+    assert!(stdout.contains("check_image spades.py \"quay.io/biocontainers/spades"));
+    assert!(!stdout.contains("brew install"));
+
+    Ok(())
+}
+
+#[test]
+fn command_batch() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("batch")
+        .arg("tests/Lambda/samples.tsv")
+        .arg("--dir")
+        .arg(tempdir.path())
+        .arg("--")
+        .arg("--parallel")
+        .arg("4")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(stderr.contains("Create 0_batch.sh"));
+    assert!(tempdir.path().join("Lambda/2_illumina/R2.fq.gz").is_file());
+    assert!(tempdir.path().join("Lambda_se/0_master.sh").is_file());
+    assert!(!tempdir
+        .path()
+        .join("Lambda_se/2_illumina/R2.fq.gz")
+        .exists());
+
+    let provenance =
+        std::fs::read_to_string(tempdir.path().join("Lambda/provenance.json")).unwrap();
+    assert!(provenance.contains("\"cov\": \"40 80\""));
+    assert!(provenance.contains("\"genome\": \"48502\""));
+    assert!(provenance.contains("\"command\": \"anchr template --parallel 4 --genome=48502"));
+    let provenance =
+        std::fs::read_to_string(tempdir.path().join("Lambda_se/provenance.json")).unwrap();
+    assert!(provenance.contains("\"se\": \"1\""));
+
+    let script = std::fs::read_to_string(tempdir.path().join("0_batch.sh")).unwrap();
+    assert!(script.contains("bash Lambda_se/0_master.sh"));
+    let script = std::fs::read_to_string(tempdir.path().join("9_stat_batch.sh")).unwrap();
+    assert!(script.contains("anchr report --merge --stat statFinal"));
+
+    // ena info
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("batch")
+        .arg("tests/Lambda/ena_info.yml")
+        .arg("--dir")
+        .arg(tempdir.path().join("ena"))
+        .output()
+        .unwrap();
+
+    let link = std::fs::read_link(tempdir.path().join("ena/Lambda/2_illumina/R1.fq.gz"))?;
+    assert!(link.ends_with("tests/Lambda/SRR5042715_1.fastq.gz"));
+
+    // values of samples replace common ones
+    let lambda = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/Lambda");
+    let sheet = tempdir.path().join("samples.tsv");
+    std::fs::write(
+        &sheet,
+        format!(
+            "name\tR1\tR2\tgenome\tparallel\nA\t{0}/R1.fq.gz\t{0}/R2.fq.gz\t48502\t2\nB\t{0}/R1.fq.gz\t{0}/R2.fq.gz\t\t\n",
+            lambda.display()
+        ),
+    )?;
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("batch")
+        .arg(&sheet)
+        .arg("--dir")
+        .arg(tempdir.path().join("opts"))
+        .arg("--")
+        .arg("--parallel")
+        .arg("24")
+        .arg("--genome=100")
+        .assert()
+        .success();
+
+    let provenance = std::fs::read_to_string(tempdir.path().join("opts/A/provenance.json"))?;
+    assert!(provenance.contains("\"parallel\": \"2\""));
+    assert!(provenance.contains("\"genome\": \"48502\""));
+    let provenance = std::fs::read_to_string(tempdir.path().join("opts/B/provenance.json"))?;
+    assert!(provenance.contains("\"parallel\": \"24\""));
+    assert!(provenance.contains("\"genome\": \"100\""));
+
+    // a bad sample doesn't stop others
+    let sheet = tempdir.path().join("bad.tsv");
+    std::fs::write(
+        &sheet,
+        format!(
+            "name\tR1\tR2\tcaller\nA\t{0}/R1.fq.gz\t{0}/R2.fq.gz\tfoo\nB\t{0}/R1.fq.gz\t{0}/R2.fq.gz\t\n",
+            lambda.display()
+        ),
+    )?;
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("batch")
+        .arg(&sheet)
+        .arg("--dir")
+        .arg(tempdir.path().join("bad"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "A: error: 'foo' isn't a valid value for '--caller <caller>'",
+        ))
+        .stderr(predicate::str::contains("1 of 2 samples failed: A"));
+
+    assert!(!tempdir.path().join("bad/A/0_master.sh").exists());
+    assert!(tempdir.path().join("bad/B/0_master.sh").is_file());
+    let script = std::fs::read_to_string(tempdir.path().join("bad/0_batch.sh")).unwrap();
+    assert!(script.contains("bash B/0_master.sh"));
+    assert!(!script.contains("bash A/0_master.sh"));
+
+    // mixed layouts
+    let yaml = tempdir.path().join("mixed.yml");
+    std::fs::write(
+        &yaml,
+        "---\nMixed:\n  SRX1:\n    library_layout: PAIRED\n    srr:\n      - SRR1\n  SRX2:\n    library_layout: SINGLE\n    srr:\n      - SRR2\n",
+    )?;
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("batch")
+        .arg(&yaml)
+        .arg("--dir")
+        .arg(tempdir.path().join("mixed"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Mixed: runs of both SINGLE and PAIRED layouts",
+        ));
+
+    // more runs than R, S and T
+    let yaml = tempdir.path().join("runs.yml");
+    std::fs::write(
+        &yaml,
+        "---\nRuns:\n  SRX1:\n    library_layout: PAIRED\n    srr:\n      - SRR1\n      - SRR2\n  SRX2:\n    library_layout: PAIRED\n    srr:\n      - SRR3\n      - SRR4\n",
+    )?;
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("batch")
+        .arg(&yaml)
+        .arg("--dir")
+        .arg(tempdir.path().join("runs"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Runs: 4 runs"));
+    assert!(!tempdir.path().join("runs/Runs").exists());
+
+    assert!(tempdir.close().is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn command_completions() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd.arg("completions").arg("bash").output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.contains("_anchr()"));
+    assert!(stdout.contains("--unitigger"));
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd.arg("completions").arg("fish").output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.contains("complete -c anchr"));
+    assert!(stdout.contains("__fish_seen_subcommand_from template"));
+
+    Ok(())
+}
+
+#[test]
+fn command_man() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd.arg("man").output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.starts_with(".TH ANCHR 1"));
+    assert!(stdout.contains(".SH \"ANCHR TEMPLATE\""));
+    assert!(stdout.contains("    anchr template [FLAGS] [OPTIONS]"));
+    assert!(stdout.contains(".SS Info\n"));
+    assert!(stdout.contains(".SS Post\\-trimming\n"));
+    assert!(stdout.contains(".SS Extend anchors\n"));
+    assert!(stdout.contains("\\-\\-unitigger"));
+    assert!(stdout.contains(".SH \"ANCHR READS FILTER\""));
+    assert!(stdout.contains("    anchr reads filter [FLAGS] [OPTIONS]"));
+
+    Ok(())
+}
+
+// Names and lengths of records in a gzipped fastq file
+fn fq_gz_records(file: &std::path::Path) -> Vec<(String, usize)> {
+    let mut content = String::new();
+    flate2::read::GzDecoder::new(std::fs::File::open(file).unwrap())
+        .read_to_string(&mut content)
+        .unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+
+    lines
+        .chunks(4)
+        .map(|c| {
+            let name = c[0].split(['/', ' ']).next().unwrap().to_string();
+            (name, c[1].len())
+        })
+        .collect()
+}
+
+#[test]
+fn command_qtrim() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("qtrim")
+        .arg("tests/Lambda/head/R1.fq.gz")
+        .arg("tests/Lambda/head/R2.fq.gz")
+        .arg("--qual")
+        .arg("25 30")
+        .arg("--len")
+        .arg("60")
+        .arg("-o")
+        .arg(tempdir.path())
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(output.status.success());
+    assert!(stderr.contains("==> Qual-Len: Q25L60"));
+    assert!(stderr.contains("==> Qual-Len: Q30L60"));
+
+    let dir = tempdir.path().join("Q25L60");
+    let r1 = fq_gz_records(&dir.join("R1.fq.gz"));
+    let r2 = fq_gz_records(&dir.join("R2.fq.gz"));
+    let rs = fq_gz_records(&dir.join("Rs.fq.gz"));
+    assert!(!r1.is_empty());
+    assert_eq!(r1.len(), r2.len());
+    assert!(r1.len() + rs.len() < 400);
+    for (a, b) in r1.iter().zip(r2.iter()) {
+        assert_eq!(a.0, b.0);
+    }
+    assert!(r1.iter().chain(&r2).chain(&rs).all(|r| r.1 >= 60));
+
+    // higher quality keeps fewer bases
+    let q30 = fq_gz_records(&tempdir.path().join("Q30L60/R1.fq.gz"));
+    assert!(q30.iter().map(|r| r.1).sum::<usize>() <= r1.iter().map(|r| r.1).sum::<usize>());
+
+    // existing results are skipped
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("qtrim")
+        .arg("tests/Lambda/head/R1.fq.gz")
+        .arg("tests/Lambda/head/R2.fq.gz")
+        .arg("--qual")
+        .arg("25")
+        .arg("-o")
+        .arg(tempdir.path())
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(output.status.success());
+    assert!(stderr.contains("R1.fq.gz already presents"));
+
+    // single end
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("qtrim")
+        .arg("tests/Lambda/head/R1.fq.gz")
+        .arg("--len")
+        .arg("90")
+        .arg("-o")
+        .arg(tempdir.path().join("se"))
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let dir = tempdir.path().join("se/Q25L90");
+    assert!(!dir.join("R2.fq.gz").exists());
+    let se = fq_gz_records(&dir.join("R1.fq.gz"));
+    assert!(!se.is_empty());
+    assert!(se.iter().all(|r| r.1 >= 90));
+
+    // gzip files of multiple members, like lane files joined by `cat`
+    let mut joined = std::fs::read("tests/Lambda/head/R1.fq.gz")?;
+    joined.extend(std::fs::read("tests/Lambda/head/R1.fq.gz")?);
+    std::fs::write(tempdir.path().join("joined.fq.gz"), &joined)?;
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("qtrim")
+        .arg(tempdir.path().join("joined.fq.gz"))
+        .arg("--len")
+        .arg("90")
+        .arg("-o")
+        .arg(tempdir.path().join("joined"))
+        .assert()
+        .success();
+    let joined = fq_gz_records(&tempdir.path().join("joined/Q25L90/R1.fq.gz"));
+    assert_eq!(joined.len(), se.len() * 2);
+
+    // failed runs leave nothing to be skipped
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("qtrim")
+        .arg("tests/Lambda/head/R1.fq.gz")
+        .arg("tests/Lambda/head/not_exist.fq.gz")
+        .arg("-o")
+        .arg(tempdir.path().join("failed"))
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("not_exist.fq.gz"));
+    assert!(!tempdir.path().join("failed/Q25L60").exists());
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("qtrim")
+        .arg(tempdir.path().join("joined.fq.gz"))
+        .arg("tests/Lambda/head/R2.fq.gz")
+        .arg("-o")
+        .arg(tempdir.path().join("failed"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("has fewer reads than"));
+    let dir = tempdir.path().join("failed/Q25L60");
+    assert_eq!(std::fs::read_dir(&dir)?.count(), 0);
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("qtrim")
+        .arg("tests/Lambda/head/R1.fq.gz")
+        .arg("tests/Lambda/head/R2.fq.gz")
+        .arg("-o")
+        .arg(tempdir.path().join("failed"))
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("already presents").not());
+    assert!(dir.join("R1.fq.gz").is_file());
+    assert!(!dir.join("R1.fq.gz.tmp").exists());
+
+    Ok(())
+}
+
+#[test]
+fn command_reads() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = TempDir::new().unwrap();
+    let path = |name: &str| tempdir.path().join(name).to_str().unwrap().to_string();
+
+    // pairs and single reads padded with N mates
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("reads")
+        .arg("interleave")
+        .arg("tests/Lambda/head/R1.fq.gz")
+        .arg("tests/Lambda/head/R2.fq.gz")
+        .arg("--prefix")
+        .arg("pe")
+        .arg("-o")
+        .arg(path("pe.fq.gz"))
+        .assert()
+        .success();
+    let pe = fq_gz_records(&tempdir.path().join("pe.fq.gz"));
+    assert_eq!(pe.len(), 400);
+    assert_eq!(pe[0].0, "@pe_0");
+    assert_eq!(pe[1].0, "@pe_0");
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("reads")
+        .arg("interleave")
+        .arg("tests/Lambda/head/R1.fq.gz")
+        .arg("--prefix")
+        .arg("se")
+        .arg("--fasta")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(stdout.lines().count(), 800);
+    assert!(stdout.starts_with(">se_0/1\nAGAGATTC"));
+    assert!(stdout.contains(">se_0/2\nN\n>se_1/1\n"));
+
+    // filter drops N mates, and repair puts their mates into orphans
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("reads")
+        .arg("filter")
+        .arg("--minlen")
+        .arg("60")
+        .arg("--fasta")
+        .arg(path("pe.fq.gz"))
+        .arg("stdin")
+        .arg("-o")
+        .arg(path("filter.fa"))
+        .with_stdin()
+        .buffer(stdout)
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("reads")
+        .arg("repair")
+        .arg(path("filter.fa"))
+        .arg("--out1")
+        .arg(path("R1.fa"))
+        .arg("--out2")
+        .arg(path("R2.fa"))
+        .arg("--outs")
+        .arg(path("Rs.fa"))
+        .assert()
+        .success();
+    let names = |name: &str| -> Vec<String> {
+        std::fs::read_to_string(path(name))
+            .unwrap()
+            .lines()
+            .filter(|l| l.starts_with('>'))
+            .map(|l| l.to_string())
+            .collect()
+    };
+    let r1 = names("R1.fa");
+    let r2 = names("R2.fa");
+    let rs = names("Rs.fa");
+    assert_eq!(r1.len(), 200);
+    assert_eq!(r2.len(), 200);
+    assert_eq!(rs.len(), 200);
+    assert_eq!(r1[0], ">pe_0/1");
+    assert_eq!(r2[0], ">pe_0/2");
+    assert_eq!(rs[0], ">se_0/1");
+
+    // mates are found wherever they are
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("reads")
+        .arg("repair")
+        .arg("stdin")
+        .arg("--out1")
+        .arg("stdout")
+        .with_stdin()
+        .buffer(">b/2\nC\n>a/1\nA\n>c\nT\n>b/1\nG\n>a/2\nT\n")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(stdout, ">b/1\nG\n>b/2\nC\n>a/1\nA\n>a/2\nT\n");
+
+    // deinterleave
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("reads")
+        .arg("deinterleave")
+        .arg(path("pe.fq.gz"))
+        .arg("--fasta")
+        .arg("--out1")
+        .arg(path("d1.fa"))
+        .arg("--out2")
+        .arg(path("d2.fa"))
+        .assert()
+        .success();
+    assert_eq!(names("d1.fa").len(), 200);
+    assert_eq!(names("d2.fa")[199], ">pe_199/2");
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.arg("reads")
+        .arg("deinterleave")
+        .arg("stdin")
+        .arg("--out1")
+        .arg(path("odd1.fa"))
+        .arg("--out2")
+        .arg(path("odd2.fa"))
+        .with_stdin()
+        .buffer(">a/1\nA\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("has no mate of a/1"));
 
     Ok(())
 }
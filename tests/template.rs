@@ -15,7 +15,7 @@ fn command_template() -> Result<(), Box<dyn std::error::Error>> {
     let output = cmd.arg("template").output().unwrap();
     let stderr = String::from_utf8(output.stderr).unwrap();
 
-    assert_eq!(stderr.lines().count(), 18);
+    assert_eq!(stderr.lines().count(), 19);
     assert!(stderr.contains("2_trim.sh"));
     assert!(&tempdir.path().join("2_trim.sh").is_file());
 
@@ -24,7 +24,7 @@ fn command_template() -> Result<(), Box<dyn std::error::Error>> {
     let output = cmd.arg("template").arg("--fastqc").output().unwrap();
     let stderr = String::from_utf8(output.stderr).unwrap();
 
-    assert_eq!(stderr.lines().count(), 19);
+    assert_eq!(stderr.lines().count(), 20);
     assert!(stderr.contains("2_fastqc.sh"));
     assert!(&tempdir.path().join("2_fastqc.sh").is_file());
 
@@ -38,7 +38,7 @@ fn command_template() -> Result<(), Box<dyn std::error::Error>> {
         .unwrap();
     let stderr = String::from_utf8(output.stderr).unwrap();
 
-    assert_eq!(stderr.lines().count(), 26);
+    assert_eq!(stderr.lines().count(), 27);
     assert!(stderr.contains("2_merge.sh"));
     assert!(&tempdir.path().join("2_merge.sh").is_file());
 
@@ -47,13 +47,672 @@ fn command_template() -> Result<(), Box<dyn std::error::Error>> {
     let output = cmd.arg("template").arg("--quorum").output().unwrap();
     let stderr = String::from_utf8(output.stderr).unwrap();
 
-    assert_eq!(stderr.lines().count(), 18);
+    assert_eq!(stderr.lines().count(), 19);
     assert!(stderr.contains("2_quorum.sh"));
     assert!(&tempdir.path().join("2_quorum.sh").is_file());
 
+    // anchr template --container docker
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("template")
+        .arg("--container")
+        .arg("docker")
+        .arg("--images")
+        .arg(curdir.join("tests/Lambda/images.tsv"))
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(stderr.lines().count(), 19);
+    let script = std::fs::read_to_string(tempdir.path().join("8_spades.sh")).unwrap();
+    assert!(script.contains("export ANCHR_CONTAINER=docker"));
+    assert!(script.contains("spades.py () { container_exec"));
+
+    // anchr template --strict --retries 2
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("template")
+        .arg("--strict")
+        .arg("--retries")
+        .arg("2")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(stderr.lines().count(), 19);
+    let script = std::fs::read_to_string(tempdir.path().join("2_trim.sh")).unwrap();
+    assert!(script.contains("set -euo pipefail"));
+    assert!(script.contains("trap 'failed"));
+    let script = std::fs::read_to_string(tempdir.path().join("0_master.sh")).unwrap();
+    assert!(script.contains("export ANCHR_RETRIES=${ANCHR_RETRIES:-2}"));
+    assert!(script.contains("retry bash 2_trim.sh"));
+
+    // anchr template --ont
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("template")
+        .arg("--ont")
+        .arg("ont.fq.gz")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(stderr.lines().count(), 23);
+    assert!(&tempdir.path().join("3_long.sh").is_file());
+    assert!(&tempdir.path().join("8_flye.sh").is_file());
+    let script = std::fs::read_to_string(tempdir.path().join("0_master.sh")).unwrap();
+    assert!(
+        script.contains("7_glue_anchors.sh 7_merge_anchors/anchor.merge.fasta 3_long/long.fasta")
+    );
+    let script = std::fs::read_to_string(tempdir.path().join("9_stat_reads.sh")).unwrap();
+    assert!(script.contains("Nanopore.filter"));
+
+    // anchr template --genome auto
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("template")
+        .arg("--genome")
+        .arg("auto")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(stderr.lines().count(), 20);
+    assert!(&tempdir.path().join("2_kmer_profile.sh").is_file());
+    let script = std::fs::read_to_string(tempdir.path().join("4_down_sampling.sh")).unwrap();
+    assert!(script.contains("2_illumina/kmer/env.json"));
+    assert!(script.contains("${GENOME_SIZE} * {3}"));
+    // stops without the genome size, other scripts run before 2_kmer_profile.sh
+    assert!(script.contains("run 2_kmer_profile.sh first"));
+    let script = std::fs::read_to_string(tempdir.path().join("2_trim.sh")).unwrap();
+    assert!(!script.contains("run 2_kmer_profile.sh first"));
+    let provenance = std::fs::read_to_string(tempdir.path().join("provenance.json")).unwrap();
+    assert!(provenance.contains("\"genome\": \"auto\""));
+
+    // no genome size for down-sampling in 2_trim.sh
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("template")
+        .arg("--genome")
+        .arg("auto")
+        .arg("--sample")
+        .arg("300")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("--sample needs a genome size"));
+
+    let output = Command::new("bash")
+        .current_dir(&tempdir)
+        .arg("4_down_sampling.sh")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("--genome auto: no GENOME_SIZE"));
+
+    // anchr template --caller
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("template")
+        .arg("--bwa")
+        .arg("Q25L60")
+        .arg("--caller")
+        .arg("both")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(stderr.lines().count(), 23);
+    assert!(&tempdir.path().join("3_gatk.sh").is_file());
+    assert!(&tempdir.path().join("3_freebayes.sh").is_file());
+    let script = std::fs::read_to_string(tempdir.path().join("0_master.sh")).unwrap();
+    assert!(script.contains("retry bash 9_stat_variants.sh"));
+
+    // anchr template --merqury
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd.arg("template").arg("--merqury").output().unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(stderr.lines().count(), 20);
+    let script = std::fs::read_to_string(tempdir.path().join("9_merqury.sh")).unwrap();
+    assert!(script.contains("meryl count k=21"));
+    let script = std::fs::read_to_string(tempdir.path().join("0_master.sh")).unwrap();
+    assert!(script.contains("retry bash 9_merqury.sh"));
+
+    // anchr template --screen
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("template")
+        .arg("--screen")
+        .arg("k2_db")
+        .arg("--taxon")
+        .arg("Escherichia coli")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(stderr.lines().count(), 19);
+    let script = std::fs::read_to_string(tempdir.path().join("2_trim.sh")).unwrap();
+    assert!(script.contains("--taxon \"Escherichia coli\""));
+    assert!(script.contains("statScreen.md"));
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .arg("template")
+        .arg("--polish")
+        .arg("--polisher")
+        .arg("racon")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(stderr.lines().count(), 20);
+    let script = std::fs::read_to_string(tempdir.path().join("7_polish.sh")).unwrap();
+    assert!(script.contains("racon -t"));
+    assert!(!script.contains("pilon"));
+    let script = std::fs::read_to_string(tempdir.path().join("0_master.sh")).unwrap();
+    assert!(script.contains("retry bash 7_polish.sh"));
+
+    // provenance.json
+    let provenance = std::fs::read_to_string(tempdir.path().join("provenance.json")).unwrap();
+    assert!(provenance.contains(env!("CARGO_PKG_VERSION")));
+    assert!(provenance.contains("\"templates\""));
+    assert!(provenance.contains("\"unitigger\": \"bcalm\""));
+
     // cleanup
     assert!(env::set_current_dir(&curdir).is_ok());
     assert!(tempdir.close().is_ok());
 
     Ok(())
 }
+
+#[test]
+fn command_template_user() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = TempDir::new().unwrap();
+    let tpl = tempdir.path().join("tpl");
+    std::fs::create_dir(&tpl)?;
+    std::fs::write(
+        tpl.join("5_mapping.tera.sh"),
+        "{# anchr: after=4_anchors when=!meta per_unitigger #}\necho {{ unitigger }}\n",
+    )?;
+    std::fs::write(
+        tpl.join("9_extra.tera.sh"),
+        "{# anchr: when=merge #}\necho extra\n",
+    )?;
+
+    // anchr template --templates tpl
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .current_dir(&tempdir)
+        .arg("template")
+        .arg("--templates")
+        .arg(&tpl)
+        .arg("--unitigger")
+        .arg("bcalm tadpole")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(stderr.lines().count(), 22);
+    let names: Vec<&str> = stderr.lines().collect();
+    let pos = |name: &str| names.iter().position(|l| l.ends_with(name)).unwrap();
+    assert_eq!(pos("4_anchors.sh") + 1, pos("5_mapping_bcalm.sh"));
+    assert!(!stderr.contains("9_extra.sh"));
+
+    let script = std::fs::read_to_string(tempdir.path().join("5_mapping_tadpole.sh")).unwrap();
+    assert!(script.contains("echo tadpole"));
+    let script = std::fs::read_to_string(tempdir.path().join("0_master.sh")).unwrap();
+    assert!(script.contains("retry bash 5_mapping_bcalm.sh"));
+    let provenance = std::fs::read_to_string(tempdir.path().join("provenance.json")).unwrap();
+    assert!(provenance.contains("user/5_mapping"));
+
+    // runs right after anchors of all unitiggers, not at the end
+    let at = |script: &str, pat: &str| script.find(pat).unwrap();
+    assert!(
+        at(&script, "9_stat_anchors.sh 4_unitigs_tadpole")
+            < at(&script, "retry bash 5_mapping_bcalm.sh")
+    );
+    assert!(at(&script, "retry bash 5_mapping_tadpole.sh") < at(&script, "7_merge_anchors.sh"));
+
+    // anchr template --templates tpl --queue mpi
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.current_dir(&tempdir)
+        .arg("template")
+        .arg("--templates")
+        .arg(&tpl)
+        .arg("--unitigger")
+        .arg("bcalm tadpole")
+        .arg("--queue")
+        .arg("mpi")
+        .assert()
+        .success();
+    let script = std::fs::read_to_string(tempdir.path().join("0_bsub.sh")).unwrap();
+    assert!(script.contains(
+        "bsub -w \"ended(${BASE_NAME}-4_unitigs_bcalm) && ended(${BASE_NAME}-4_unitigs_tadpole)\" \\\n    -q mpi -n 8 -J \"${BASE_NAME}-5_mapping_bcalm\""
+    ));
+    assert!(
+        at(&script, "-J \"${BASE_NAME}-5_mapping_tadpole\"") < at(&script, "7_merge_anchors.sh")
+    );
+
+    // anchr template --templates tpl --plan
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .current_dir(&tempdir)
+        .arg("template")
+        .arg("--templates")
+        .arg(&tpl)
+        .arg("--unitigger")
+        .arg("bcalm tadpole")
+        .arg("--plan")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let names: Vec<&str> = stdout.lines().filter(|l| !l.starts_with(' ')).collect();
+    let pos = |name: &str| names.iter().position(|l| *l == name).unwrap();
+    assert_eq!(pos("4_unitigs_tadpole.sh") + 1, pos("5_mapping_bcalm.sh"));
+    assert!(pos("5_mapping_tadpole.sh") < pos("7_merge_anchors.sh"));
+
+    // replaces a built-in stage
+    std::fs::write(tpl.join("9_quast.tera.sh"), "echo my quast\n")?;
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .current_dir(&tempdir)
+        .arg("template")
+        .arg("--templates")
+        .arg(&tpl)
+        .arg("--merge")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(stderr.lines().count(), 28);
+    assert!(stderr.contains("9_extra.sh"));
+    let script = std::fs::read_to_string(tempdir.path().join("9_quast.sh")).unwrap();
+    assert_eq!(script, "echo my quast\n\n");
+
+    // a key of `when` missing from opt
+    std::fs::write(
+        tpl.join("9_extra.tera.sh"),
+        "{# anchr: when=busco #}\necho extra\n",
+    )?;
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.current_dir(&tempdir)
+        .arg("template")
+        .arg("--templates")
+        .arg(&tpl)
+        .arg("--busco")
+        .assert()
+        .success();
+    assert!(&tempdir.path().join("9_extra.sh").is_file());
+
+    std::fs::write(
+        tpl.join("9_extra.tera.sh"),
+        "{# anchr: when=!buscoo #}\necho extra\n",
+    )?;
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .current_dir(&tempdir)
+        .arg("template")
+        .arg("--templates")
+        .arg(&tpl)
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Unknown metadata when=!buscoo of the template 9_extra"));
+    std::fs::write(
+        tpl.join("9_extra.tera.sh"),
+        "{# anchr: when=merge #}\necho extra\n",
+    )?;
+
+    // unknown metadata
+    std::fs::write(tpl.join("bad.tera.sh"), "{# anchr: foo #}\n")?;
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .current_dir(&tempdir)
+        .arg("template")
+        .arg("--templates")
+        .arg(&tpl)
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Unknown metadata"));
+
+    Ok(())
+}
+
+#[test]
+fn template_user_hooks() {
+    // 0_master.sh and 0_bsub.sh run user stages after the same built-in ones
+    let hooks = |tpl: &'static str| {
+        let mut hooks: Vec<&str> = tpl
+            .split("after=[")
+            .skip(1)
+            .flat_map(|part| part.split(']').next().unwrap().split(", "))
+            .collect();
+        hooks.sort_unstable();
+        hooks.dedup();
+        hooks
+    };
+    let master = hooks(include_str!("../templates/0_master.tera.sh"));
+    let bsub = hooks(include_str!("../templates/0_bsub.tera.sh"));
+
+    let mut stages: Vec<String> = anchr::libs::template::STAGES
+        .iter()
+        .filter(|s| !s.manual)
+        .map(|s| format!("\"{}\"", s.name))
+        .collect();
+    stages.sort_unstable();
+    stages.dedup();
+
+    assert!(master.contains(&"\"9_stat_final\""));
+    assert_eq!(master, stages);
+    assert_eq!(bsub, stages);
+}
+
+#[test]
+fn command_template_check() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = TempDir::new().unwrap();
+    let tpl = tempdir.path().join("tpl");
+    std::fs::create_dir(&tpl)?;
+    std::fs::write(
+        tpl.join("9_extra.tera.sh"),
+        "{# anchr: when=merge #}\nbbduk.sh in={{ opt.sample | replace(from=\"0\", to=\"\") }} out=a.fq\nFOO= echo {{ opt.parallel }}\nlocal x=\nif [ 1 ]; then\n",
+    )?;
+
+    // anchr template --check
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .current_dir(&tempdir)
+        .arg("template")
+        .arg("--check")
+        .arg("--templates")
+        .arg(&tpl)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("of 192 combinations"));
+    // 96 combinations with --merge, 2 problems each
+    assert_eq!(stdout.lines().count(), 192);
+    assert!(stdout.contains(
+        "[pe merge quorum tadpole extend busco queue] 9_extra.sh: line 2: empty option in="
+    ));
+    assert!(stdout.contains("syntax error"));
+    assert!(!&tempdir.path().join("2_trim.sh").exists());
+
+    Ok(())
+}
+
+#[test]
+fn command_template_plan() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = TempDir::new().unwrap();
+    std::fs::create_dir(tempdir.path().join("2_illumina"))?;
+    std::fs::write(tempdir.path().join("2_illumina/R1.fq.gz"), "")?;
+
+    // anchr template --plan
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .current_dir(&tempdir)
+        .arg("template")
+        .arg("--plan")
+        .arg("--merge")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stdout.contains("2_illumina/R1.fq.gz [present]"));
+    assert!(stdout.contains("2_illumina/R2.fq.gz [MISSING]"));
+    assert!(stdout.contains("2_illumina/merge/pe.cor.fa.gz [from 2_merge.sh]"));
+    assert!(stdout.contains("tools: anchr bbtools"));
+    assert!(!&tempdir.path().join("2_trim.sh").exists());
+
+    // 2_trim.sh runs before 2_quorum.sh
+    let pos = |name: &str| stdout.lines().position(|l| l == name).unwrap();
+    assert!(pos("2_trim.sh") < pos("2_quorum.sh"));
+    assert!(pos("7_merge_anchors.sh") < pos("9_quast.sh"));
+
+    // anchr template --plan --se
+    let mut cmd = Command::cargo_bin("anchr")?;
+    let output = cmd
+        .current_dir(&tempdir)
+        .arg("template")
+        .arg("--plan")
+        .arg("--se")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(!stdout.contains("R2.fq.gz"));
+
+    Ok(())
+}
+
+// Stand-ins of external tools, enough to run stages on the fixtures
+#[cfg(unix)]
+const STUB_TOOLS: &[(&str, &str)] = &[
+    (
+        "dazz",
+        r#"#!/usr/bin/env bash
+# every sequence is contained, so all of them are reported as from the first file
+OUT=stdout
+while [ $# -gt 0 ]; do
+    if [ "$1" == "-o" ]; then OUT=$2; shift; fi
+    shift
+done
+if [ "${OUT}" == "stdout" ]; then
+    printf ">infile_0/1/0_1000\nACGT\n"
+else
+    printf ">infile_0/1/0_1000\nACGT\n" > "${OUT}"
+fi
+"#,
+    ),
+    (
+        "faops",
+        r#"#!/usr/bin/env bash
+OUT="${@: -1}"
+if [ "$1" == "n50" ]; then
+    printf "4\n4\n1\n"
+elif [ "$1" == "some" ]; then
+    : > "${OUT}"
+elif [ "${OUT}" == "stdout" ]; then
+    cat
+else
+    cat > "${OUT}"
+fi
+"#,
+    ),
+    (
+        "parallel",
+        r#"#!/usr/bin/env bash
+while [[ "$1" == -* ]]; do
+    if [ "$1" == "-j" ]; then shift; fi
+    shift
+done
+xargs -r "$@"
+"#,
+    ),
+];
+
+#[cfg(unix)]
+fn copy_dir(from: &std::path::Path, to: &std::path::Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &to.join(entry.file_name()))?;
+        } else {
+            std::fs::copy(entry.path(), to.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn command_template_strict() -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tempdir = TempDir::new().unwrap();
+    let lambda = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/Lambda");
+
+    let bin = tempdir.path().join("bin");
+    std::fs::create_dir(&bin)?;
+    for (name, content) in STUB_TOOLS {
+        std::fs::write(bin.join(name), content)?;
+        std::fs::set_permissions(bin.join(name), std::fs::Permissions::from_mode(0o755))?;
+    }
+    let anchr = std::path::Path::new(env!("CARGO_BIN_EXE_anchr"));
+    let path = format!(
+        "{}:{}:{}",
+        bin.display(),
+        anchr.parent().unwrap().display(),
+        env::var("PATH")?
+    );
+
+    let project = tempdir.path().join("Lambda");
+    copy_dir(
+        &lambda.join("4_unitigs_bcalm"),
+        &project.join("4_unitigs_bcalm"),
+    )?;
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.current_dir(&project)
+        .arg("template")
+        .arg("--strict")
+        .arg("--extend")
+        .arg("--genome")
+        .arg("48502")
+        .arg("--parallel")
+        .arg("1")
+        .assert()
+        .success();
+
+    // all of the others are contained
+    let output = Command::new("bash")
+        .current_dir(&project)
+        .env("PATH", &path)
+        .arg("7_merge_anchors.sh")
+        .output()?;
+    assert!(output.status.success());
+    assert!(!project.join("7_merge_anchors.sh.failed").exists());
+    assert!(project
+        .join("7_merge_anchors/others.non-contained.fasta")
+        .is_file());
+
+    // raw numbers next to the Markdown table
+    let output = Command::new("bash")
+        .current_dir(&project)
+        .env("PATH", &path)
+        .arg("9_stat_final.sh")
+        .output()?;
+    assert!(output.status.success());
+    let tsv = std::fs::read_to_string(project.join("statFinal.tsv"))?;
+    assert_eq!(
+        tsv,
+        "name\tn50_bp\tsum_bp\tcount\n7_merge_anchors.anchors\t4\t4\t1\n7_merge_anchors.others\t4\t4\t1\n"
+    );
+    let md = std::fs::read_to_string(project.join("statFinal.md"))?;
+    assert!(md.contains("| 7_merge_anchors.others | 4 | 4 | 1 |"));
+    assert!(project.join("statFinal.json").is_file());
+
+    // columns from statMerqury.tsv of 9_merqury.sh
+    std::fs::write(
+        project.join("statMerqury.tsv"),
+        "name\tqv\tcompleteness_pct\tmapped_pct\n7_merge_anchors.anchors\t35.2\t98.50\t99.1\n",
+    )?;
+    let output = Command::new("bash")
+        .current_dir(&project)
+        .env("PATH", &path)
+        .arg("9_stat_final.sh")
+        .output()?;
+    assert!(output.status.success());
+    let tsv = std::fs::read_to_string(project.join("statFinal.tsv"))?;
+    assert!(tsv.starts_with("name\tn50_bp\tsum_bp\tcount\tqv\tcompleteness_pct\tmapped_pct\n"));
+    assert!(tsv.contains("\n7_merge_anchors.others\t4\t4\t1\t\t\t\n"));
+    let md = std::fs::read_to_string(project.join("statFinal.md"))?;
+    assert!(md.contains("| 7_merge_anchors.anchors | 4 | 4 | 1 | 35.2 | 98.50% | 99.1% |"));
+    assert!(md.contains("| 7_merge_anchors.others | 4 | 4 | 1 | - | - | - |"));
+
+    // only one of the assemblers has outputs
+    std::fs::create_dir(project.join("8_megahit"))?;
+    std::fs::write(
+        project.join("8_megahit/megahit.non-contained.fasta"),
+        ">contig\nACGT\n",
+    )?;
+    for entry in std::fs::read_dir(&project)? {
+        let name = entry?.file_name().into_string().unwrap();
+        if name.ends_with(".sh") && !name.starts_with("0_") {
+            std::fs::remove_file(project.join(&name))?;
+        }
+    }
+
+    let output = Command::new("bash")
+        .current_dir(&project)
+        .env("PATH", &path)
+        .arg("0_master.sh")
+        .output()?;
+    assert!(output.status.success());
+    assert!(!project.join("0_master.sh.failed").exists());
+    assert_eq!(
+        std::fs::read_to_string(project.join("7_extend_anchors/contigs.2GS.fasta"))?,
+        ">contig\nACGT\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn command_template_provenance() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = TempDir::new().unwrap();
+    let tpl = tempdir.path().join("tpl");
+    std::fs::create_dir(&tpl)?;
+    std::fs::write(
+        tpl.join("5_noop.tera.sh"),
+        "{%- include \"header\" -%}\nsave_version bash\n",
+    )?;
+
+    let mut cmd = Command::cargo_bin("anchr")?;
+    cmd.current_dir(&tempdir)
+        .arg("template")
+        .arg("--templates")
+        .arg(&tpl)
+        .assert()
+        .success();
+
+    // stages running at the same time don't lose updates of each other
+    let children = (0..16)
+        .map(|i| {
+            Command::new("bash")
+                .current_dir(&tempdir)
+                .arg("5_noop.sh")
+                .arg(i.to_string())
+                .spawn()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    for mut child in children {
+        assert!(child.wait()?.success());
+    }
+
+    let provenance: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(
+        tempdir.path().join("provenance.json"),
+    )?)?;
+    assert_eq!(provenance["stages"].as_array().unwrap().len(), 16);
+    assert!(provenance["tools"]["bash"].is_string());
+
+    Ok(())
+}